@@ -68,6 +68,12 @@ pub enum MrjCommand {
         /// Whether to show information for PRs where head doesn't match configured pattern
         #[arg(long = "show-unmatched-head-prs", short = 'H')]
         show_prs_with_unmatched_head: bool,
+        /// Whether to show information for draft PRs
+        #[arg(long = "show-draft-prs", short = 'd')]
+        show_draft_prs: bool,
+        /// Whether to show information for PRs older than the configured maximum age
+        #[arg(long = "show-stale-prs", short = 'S')]
+        show_stale_prs: bool,
         /// Whether to actually merge PRs; mrj operates in "dry-run mode" by default
         #[arg(long = "execute", short = 'e')]
         execute: bool,
@@ -155,6 +161,8 @@ impl std::fmt::Display for Args {
                 show_repos_with_no_prs,
                 show_prs_from_untrusted_authors,
                 show_prs_with_unmatched_head,
+                show_draft_prs,
+                show_stale_prs,
                 execute,
                 plain_stdout,
             } => format!(
@@ -170,6 +178,8 @@ skip disqualifications in summary:    {}
 show repos with no prs:               {}
 show prs from untrusted authors:      {}
 show prs with unmatched head:         {}
+show draft prs:                       {}
+show stale prs:                       {}
 execute:                              {}
 plain stdout:                         {}
 "#,
@@ -183,6 +193,8 @@ plain stdout:                         {}
                 show_repos_with_no_prs,
                 show_prs_from_untrusted_authors,
                 show_prs_with_unmatched_head,
+                show_draft_prs,
+                show_stale_prs,
                 execute,
                 plain_stdout,
             ),
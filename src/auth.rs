@@ -3,6 +3,7 @@ use std::env::VarError;
 use std::process::Command;
 
 const TOKEN_ENV_VAR: &str = "MRJ_TOKEN";
+const APPROVAL_TOKEN_ENV_VAR: &str = "MRJ_APPROVAL_TOKEN";
 
 pub fn get_token() -> anyhow::Result<String> {
     let token = std::env::var(TOKEN_ENV_VAR).or_else(|err|
@@ -15,8 +16,9 @@ mrj tries to get this token in the following order:
 - Running "gh auth token" (this failed)
 
 Make sure mrj can get a token from either one of these approaches, and that the token has the following permissions for the relevant repos:
-- Read access to checks, metadata, and pull requests
-- Read and write access to code"#
+- Read access to checks, commit statuses, metadata, and pull requests
+- Read and write access to code
+- Read and write access to pull requests (only needed if "auto_approve", "update_behind_branches", "request_rebases", or "close_superseded" is on, or if "merge_strategy" isn't "direct")"#
             )),
             VarError::NotUnicode(_) => Err(anyhow::anyhow!("{} is not valid unicode", TOKEN_ENV_VAR))
         })?;
@@ -24,6 +26,19 @@ Make sure mrj can get a token from either one of these approaches, and that the
     Ok(token)
 }
 
+/// Gets an optional, separate token to submit approving reviews with; GitHub doesn't let users
+/// approve their own PRs.
+pub fn get_approval_token() -> anyhow::Result<Option<String>> {
+    match std::env::var(APPROVAL_TOKEN_ENV_VAR) {
+        Ok(token) => Ok(Some(token)),
+        Err(VarError::NotPresent) => Ok(None),
+        Err(VarError::NotUnicode(_)) => Err(anyhow::anyhow!(
+            "{} is not valid unicode",
+            APPROVAL_TOKEN_ENV_VAR
+        )),
+    }
+}
+
 fn get_token_from_gh() -> anyhow::Result<String> {
     let output = Command::new("gh")
         .args(["auth", "token"])
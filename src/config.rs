@@ -1,27 +1,480 @@
-use crate::domain::{HeadPattern, MergeType, Repo, SortBy, SortDirection};
+use crate::domain::{
+    CheckPattern, CommitTemplate, Glob, HeadPattern, HumanDuration, MergeStrategy, MergeType,
+    PackageVersion, Repo, RepoSelector, SortBy, SortDirection, WaitForPendingChecks,
+};
+use crate::rule::Rule;
+use crate::upgrade::UpdateType;
 use anyhow::Context;
-use serde::Deserialize;
+use serde::de::{self, MapAccess, SeqAccess, Visitor, value::SeqAccessDeserializer};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::PathBuf;
 
 #[derive(Debug, Deserialize)]
 #[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
 pub struct Config {
-    pub repos: Vec<Repo>,
+    #[serde(default, deserialize_with = "deserialize_repos")]
+    pub repos: Vec<RepoSelector>,
+    pub search: Option<String>,
+    #[serde(default)]
+    pub exclude: Vec<Glob>,
+    #[serde(default = "default_true")]
+    pub skip_archived: bool,
+    #[serde(default = "default_true")]
+    pub skip_forks: bool,
     pub trusted_authors: Vec<String>,
     pub base_branch: Option<String>,
+    #[serde(default)]
+    pub base_branches: Vec<Glob>,
     #[serde(skip_serializing)]
     pub head_pattern: Option<HeadPattern>,
     #[serde(default = "default_false")]
     pub merge_if_blocked: bool,
+    #[serde(default = "default_false")]
+    pub update_behind_branches: bool,
+    #[serde(default = "default_false")]
+    pub request_rebases: bool,
     #[serde(default = "default_true")]
     pub merge_if_checks_skipped: bool,
     #[serde(default = "default_false")]
     pub merge_if_checks_neutral: bool,
+    #[serde(default)]
+    pub required_checks: Vec<CheckPattern>,
+    #[serde(default)]
+    pub min_checks: usize,
+    #[serde(default)]
+    pub ignored_checks: Vec<CheckPattern>,
+    pub wait_for_pending_checks: Option<WaitForPendingChecks>,
+    #[serde(default)]
+    pub required_labels: Vec<String>,
+    #[serde(default)]
+    pub blocking_labels: Vec<String>,
+    pub freeze_label: Option<String>,
+    #[serde(default = "default_false")]
+    pub require_approval: bool,
+    #[serde(default)]
+    pub trusted_reviewers: Vec<String>,
+    #[serde(default = "default_false")]
+    pub auto_approve: bool,
+    pub min_pr_age: Option<HumanDuration>,
+    #[serde(default = "default_true")]
+    pub skip_drafts: bool,
+    pub max_pr_age: Option<HumanDuration>,
+    pub allowed_update_types: Option<Vec<UpdateType>>,
+    #[serde(default)]
+    pub allow_packages: Vec<Glob>,
+    #[serde(default)]
+    pub deny_packages: Vec<Glob>,
+    #[serde(default)]
+    pub deny_versions: Vec<PackageVersion>,
+    #[serde(default)]
+    pub ecosystems: Vec<String>,
+    #[serde(default)]
+    pub allowed_paths: Vec<Glob>,
+    pub max_changed_files: Option<u64>,
+    pub max_additions: Option<u64>,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
     pub merge_type: MergeType,
+    #[serde(default = "default_merge_strategy")]
+    pub merge_strategy: MergeStrategy,
+    pub commit_title_template: Option<CommitTemplate>,
+    pub commit_message_template: Option<CommitTemplate>,
+    #[serde(default = "default_max_merges_per_repo")]
+    pub max_merges_per_repo: usize,
+    pub merge_delay: Option<HumanDuration>,
+    #[serde(default = "default_false")]
+    pub delete_branch_after_merge: bool,
+    #[serde(default = "default_false")]
+    pub close_superseded: bool,
     #[serde(default = "default_sort")]
     pub sort_by: SortBy,
     #[serde(default = "default_sort_direction")]
     pub sort_direction: SortDirection,
+    #[serde(default = "default_max_pages")]
+    pub max_pages: u32,
+    #[serde(default, rename = "owner", alias = "owners")]
+    pub owner_overrides: BTreeMap<String, SettingsOverride>,
+    #[serde(default, rename = "repo")]
+    pub repo_overrides: BTreeMap<String, SettingsOverride>,
+    /// In the order they're declared in, which is the order they're applied in
+    #[serde(
+        default,
+        rename = "base",
+        deserialize_with = "deserialize_base_overrides"
+    )]
+    pub base_overrides: Vec<(Glob, SettingsOverride)>,
+    #[serde(default, rename = "ecosystem")]
+    pub ecosystem_overrides: BTreeMap<String, SettingsOverride>,
+}
+
+/// Settings that can be overridden for all repos of an owner (via `[owner."<owner>"]`, or
+/// `[owners."<owner>"]`), for a single repo (via `[repo."<owner>/<repo>"]`), for PRs against
+/// matching base branches (via `[base."<pattern>"]`), or for PRs of an ecosystem (via
+/// `[ecosystem."<ecosystem>"]`).
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct SettingsOverride {
+    pub trusted_authors: Option<Vec<String>>,
+    pub base_branch: Option<String>,
+    pub base_branches: Option<Vec<Glob>>,
+    #[serde(skip_serializing)]
+    pub head_pattern: Option<HeadPattern>,
+    pub merge_if_blocked: Option<bool>,
+    pub update_behind_branches: Option<bool>,
+    pub request_rebases: Option<bool>,
+    pub merge_if_checks_skipped: Option<bool>,
+    pub merge_if_checks_neutral: Option<bool>,
+    pub required_checks: Option<Vec<CheckPattern>>,
+    pub min_checks: Option<usize>,
+    pub ignored_checks: Option<Vec<CheckPattern>>,
+    pub wait_for_pending_checks: Option<WaitForPendingChecks>,
+    pub required_labels: Option<Vec<String>>,
+    pub blocking_labels: Option<Vec<String>>,
+    pub freeze_label: Option<String>,
+    pub require_approval: Option<bool>,
+    pub trusted_reviewers: Option<Vec<String>>,
+    pub auto_approve: Option<bool>,
+    pub min_pr_age: Option<HumanDuration>,
+    pub skip_drafts: Option<bool>,
+    pub max_pr_age: Option<HumanDuration>,
+    pub allowed_update_types: Option<Vec<UpdateType>>,
+    pub allow_packages: Option<Vec<Glob>>,
+    pub deny_packages: Option<Vec<Glob>>,
+    pub deny_versions: Option<Vec<PackageVersion>>,
+    pub ecosystems: Option<Vec<String>>,
+    pub allowed_paths: Option<Vec<Glob>>,
+    pub max_changed_files: Option<u64>,
+    pub max_additions: Option<u64>,
+    pub rules: Option<Vec<Rule>>,
+    pub merge_type: Option<MergeType>,
+    pub merge_strategy: Option<MergeStrategy>,
+    pub commit_title_template: Option<CommitTemplate>,
+    pub commit_message_template: Option<CommitTemplate>,
+    pub max_merges_per_repo: Option<usize>,
+    pub merge_delay: Option<HumanDuration>,
+    pub delete_branch_after_merge: Option<bool>,
+    pub close_superseded: Option<bool>,
+}
+
+/// The settings in effect for a single repo, after owner and repo overrides have been layered
+/// over the global ones.
+#[derive(Debug, Clone)]
+pub struct RepoSettings {
+    pub trusted_authors: Vec<String>,
+    pub base_branch: Option<String>,
+    pub base_branches: Vec<Glob>,
+    pub head_pattern: Option<HeadPattern>,
+    pub merge_if_blocked: bool,
+    pub update_behind_branches: bool,
+    pub request_rebases: bool,
+    pub merge_if_checks_skipped: bool,
+    pub merge_if_checks_neutral: bool,
+    pub required_checks: Vec<CheckPattern>,
+    pub min_checks: usize,
+    pub ignored_checks: Vec<CheckPattern>,
+    pub wait_for_pending_checks: Option<WaitForPendingChecks>,
+    pub required_labels: Vec<String>,
+    pub blocking_labels: Vec<String>,
+    pub freeze_label: Option<String>,
+    pub require_approval: bool,
+    pub trusted_reviewers: Vec<String>,
+    pub auto_approve: bool,
+    pub min_pr_age: Option<HumanDuration>,
+    pub skip_drafts: bool,
+    pub max_pr_age: Option<HumanDuration>,
+    pub allowed_update_types: Option<Vec<UpdateType>>,
+    pub allow_packages: Vec<Glob>,
+    pub deny_packages: Vec<Glob>,
+    pub deny_versions: Vec<PackageVersion>,
+    pub ecosystems: Vec<String>,
+    pub allowed_paths: Vec<Glob>,
+    pub max_changed_files: Option<u64>,
+    pub max_additions: Option<u64>,
+    pub rules: Vec<Rule>,
+    pub merge_type: MergeType,
+    pub merge_strategy: MergeStrategy,
+    pub commit_title_template: Option<CommitTemplate>,
+    pub commit_message_template: Option<CommitTemplate>,
+    pub max_merges_per_repo: usize,
+    pub merge_delay: Option<HumanDuration>,
+    pub delete_branch_after_merge: bool,
+    pub close_superseded: bool,
+}
+
+impl RepoSettings {
+    fn apply(&mut self, o: &SettingsOverride) {
+        if let Some(trusted_authors) = &o.trusted_authors {
+            self.trusted_authors = trusted_authors.clone();
+        }
+        if let Some(base_branch) = &o.base_branch {
+            self.base_branch = Some(base_branch.clone());
+        }
+        if let Some(base_branches) = &o.base_branches {
+            self.base_branches = base_branches.clone();
+        }
+        if let Some(head_pattern) = &o.head_pattern {
+            self.head_pattern = Some(head_pattern.clone());
+        }
+        if let Some(merge_if_blocked) = o.merge_if_blocked {
+            self.merge_if_blocked = merge_if_blocked;
+        }
+        if let Some(update_behind_branches) = o.update_behind_branches {
+            self.update_behind_branches = update_behind_branches;
+        }
+        if let Some(request_rebases) = o.request_rebases {
+            self.request_rebases = request_rebases;
+        }
+        if let Some(merge_if_checks_skipped) = o.merge_if_checks_skipped {
+            self.merge_if_checks_skipped = merge_if_checks_skipped;
+        }
+        if let Some(merge_if_checks_neutral) = o.merge_if_checks_neutral {
+            self.merge_if_checks_neutral = merge_if_checks_neutral;
+        }
+        if let Some(required_checks) = &o.required_checks {
+            self.required_checks = required_checks.clone();
+        }
+        if let Some(min_checks) = o.min_checks {
+            self.min_checks = min_checks;
+        }
+        if let Some(ignored_checks) = &o.ignored_checks {
+            self.ignored_checks = ignored_checks.clone();
+        }
+        if let Some(wait_for_pending_checks) = &o.wait_for_pending_checks {
+            self.wait_for_pending_checks = Some(wait_for_pending_checks.clone());
+        }
+        if let Some(required_labels) = &o.required_labels {
+            self.required_labels = required_labels.clone();
+        }
+        if let Some(blocking_labels) = &o.blocking_labels {
+            self.blocking_labels = blocking_labels.clone();
+        }
+        if let Some(freeze_label) = &o.freeze_label {
+            self.freeze_label = Some(freeze_label.clone());
+        }
+        if let Some(require_approval) = o.require_approval {
+            self.require_approval = require_approval;
+        }
+        if let Some(trusted_reviewers) = &o.trusted_reviewers {
+            self.trusted_reviewers = trusted_reviewers.clone();
+        }
+        if let Some(auto_approve) = o.auto_approve {
+            self.auto_approve = auto_approve;
+        }
+        if let Some(min_pr_age) = &o.min_pr_age {
+            self.min_pr_age = Some(min_pr_age.clone());
+        }
+        if let Some(skip_drafts) = o.skip_drafts {
+            self.skip_drafts = skip_drafts;
+        }
+        if let Some(max_pr_age) = &o.max_pr_age {
+            self.max_pr_age = Some(max_pr_age.clone());
+        }
+        if let Some(allowed_update_types) = &o.allowed_update_types {
+            self.allowed_update_types = Some(allowed_update_types.clone());
+        }
+        if let Some(allow_packages) = &o.allow_packages {
+            self.allow_packages = allow_packages.clone();
+        }
+        if let Some(deny_packages) = &o.deny_packages {
+            self.deny_packages = deny_packages.clone();
+        }
+        if let Some(deny_versions) = &o.deny_versions {
+            self.deny_versions = deny_versions.clone();
+        }
+        if let Some(ecosystems) = &o.ecosystems {
+            self.ecosystems = ecosystems.clone();
+        }
+        if let Some(allowed_paths) = &o.allowed_paths {
+            self.allowed_paths = allowed_paths.clone();
+        }
+        if let Some(max_changed_files) = o.max_changed_files {
+            self.max_changed_files = Some(max_changed_files);
+        }
+        if let Some(max_additions) = o.max_additions {
+            self.max_additions = Some(max_additions);
+        }
+        if let Some(rules) = &o.rules {
+            self.rules = rules.clone();
+        }
+        if let Some(merge_type) = &o.merge_type {
+            self.merge_type = merge_type.clone();
+        }
+        if let Some(merge_strategy) = o.merge_strategy {
+            self.merge_strategy = merge_strategy;
+        }
+        if let Some(commit_title_template) = &o.commit_title_template {
+            self.commit_title_template = Some(commit_title_template.clone());
+        }
+        if let Some(commit_message_template) = &o.commit_message_template {
+            self.commit_message_template = Some(commit_message_template.clone());
+        }
+        if let Some(max_merges_per_repo) = o.max_merges_per_repo {
+            self.max_merges_per_repo = max_merges_per_repo;
+        }
+        if let Some(merge_delay) = &o.merge_delay {
+            self.merge_delay = Some(merge_delay.clone());
+        }
+        if let Some(delete_branch_after_merge) = o.delete_branch_after_merge {
+            self.delete_branch_after_merge = delete_branch_after_merge;
+        }
+        if let Some(close_superseded) = o.close_superseded {
+            self.close_superseded = close_superseded;
+        }
+    }
+}
+
+impl Config {
+    pub fn settings_for(&self, repo: &Repo) -> RepoSettings {
+        let mut settings = RepoSettings {
+            trusted_authors: self.trusted_authors.clone(),
+            base_branch: self.base_branch.clone(),
+            base_branches: self.base_branches.clone(),
+            head_pattern: self.head_pattern.clone(),
+            merge_if_blocked: self.merge_if_blocked,
+            update_behind_branches: self.update_behind_branches,
+            request_rebases: self.request_rebases,
+            merge_if_checks_skipped: self.merge_if_checks_skipped,
+            merge_if_checks_neutral: self.merge_if_checks_neutral,
+            required_checks: self.required_checks.clone(),
+            min_checks: self.min_checks,
+            ignored_checks: self.ignored_checks.clone(),
+            wait_for_pending_checks: self.wait_for_pending_checks.clone(),
+            required_labels: self.required_labels.clone(),
+            blocking_labels: self.blocking_labels.clone(),
+            freeze_label: self.freeze_label.clone(),
+            require_approval: self.require_approval,
+            trusted_reviewers: self.trusted_reviewers.clone(),
+            auto_approve: self.auto_approve,
+            min_pr_age: self.min_pr_age.clone(),
+            skip_drafts: self.skip_drafts,
+            max_pr_age: self.max_pr_age.clone(),
+            allowed_update_types: self.allowed_update_types.clone(),
+            allow_packages: self.allow_packages.clone(),
+            deny_packages: self.deny_packages.clone(),
+            deny_versions: self.deny_versions.clone(),
+            ecosystems: self.ecosystems.clone(),
+            allowed_paths: self.allowed_paths.clone(),
+            max_changed_files: self.max_changed_files,
+            max_additions: self.max_additions,
+            rules: self.rules.clone(),
+            merge_type: self.merge_type.clone(),
+            merge_strategy: self.merge_strategy,
+            commit_title_template: self.commit_title_template.clone(),
+            commit_message_template: self.commit_message_template.clone(),
+            max_merges_per_repo: self.max_merges_per_repo,
+            merge_delay: self.merge_delay.clone(),
+            delete_branch_after_merge: self.delete_branch_after_merge,
+            close_superseded: self.close_superseded,
+        };
+
+        if let Some(o) = self.owner_overrides.get(&repo.owner) {
+            settings.apply(o);
+        }
+
+        if let Some(o) = self.repo_overrides.get(&repo.to_string()) {
+            settings.apply(o);
+        }
+
+        settings
+    }
+
+    /// Like `settings_for`, with the overrides for the base branch of a PR (in the order they're
+    /// declared in), and then for its ecosystem layered on top.
+    pub fn settings_for_pr(
+        &self,
+        repo: &Repo,
+        base: &str,
+        ecosystem: Option<&str>,
+    ) -> RepoSettings {
+        let mut settings = self.settings_for(repo);
+
+        for (pattern, o) in &self.base_overrides {
+            if pattern.is_match(base) {
+                settings.apply(o);
+            }
+        }
+
+        if let Some(o) = ecosystem.and_then(|e| self.ecosystem_overrides.get(e)) {
+            settings.apply(o);
+        }
+
+        settings
+    }
+}
+
+/// `repos` lists the repos to run for; a `[repos."<owner>/<repo>"]` table is likely meant to be a
+/// repo override, so it gets pointed to the right table, rather than a generic type error.
+fn deserialize_repos<'de, D>(deserializer: D) -> Result<Vec<RepoSelector>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct ReposVisitor;
+
+    impl<'de> Visitor<'de> for ReposVisitor {
+        type Value = Vec<RepoSelector>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a list of repos")
+        }
+
+        fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            Vec::deserialize(SeqAccessDeserializer::new(seq))
+        }
+
+        fn visit_map<A>(self, _map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            Err(de::Error::custom(
+                r#"repos needs to be a list of repos; settings for a single repo go in a [repo."<owner>/<repo>"] table"#,
+            ))
+        }
+    }
+
+    deserializer.deserialize_any(ReposVisitor)
+}
+
+/// Base branch overrides are applied in the order they're declared in (which the TOML parser keeps
+/// track of), so they're collected into a list, with their patterns compiled once.
+fn deserialize_base_overrides<'de, D>(
+    deserializer: D,
+) -> Result<Vec<(Glob, SettingsOverride)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct BaseOverridesVisitor;
+
+    impl<'de> Visitor<'de> for BaseOverridesVisitor {
+        type Value = Vec<(Glob, SettingsOverride)>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a table of base branch patterns")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut overrides = vec![];
+            while let Some((key, o)) = map.next_entry::<String, SettingsOverride>()? {
+                let pattern = Glob::new(&key).map_err(|err| {
+                    de::Error::custom(format!("invalid base override \"{key}\": {err}"))
+                })?;
+                overrides.push((pattern, o));
+            }
+
+            Ok(overrides)
+        }
+    }
+
+    deserializer.deserialize_map(BaseOverridesVisitor)
 }
 
 fn default_true() -> bool {
@@ -32,6 +485,10 @@ fn default_false() -> bool {
     false
 }
 
+fn default_merge_strategy() -> MergeStrategy {
+    MergeStrategy::Direct
+}
+
 fn default_sort() -> SortBy {
     SortBy::Created
 }
@@ -40,6 +497,14 @@ fn default_sort_direction() -> SortDirection {
     SortDirection::Ascending
 }
 
+fn default_max_merges_per_repo() -> usize {
+    1
+}
+
+fn default_max_pages() -> u32 {
+    10
+}
+
 pub fn get_config(config_path: PathBuf) -> anyhow::Result<Config> {
     let config_str = std::fs::read_to_string(&config_path).with_context(|| {
         format!(
@@ -55,13 +520,67 @@ pub fn get_config(config_path: PathBuf) -> anyhow::Result<Config> {
 fn parse_config(config_str: &str) -> anyhow::Result<Config> {
     let config: Config = toml::from_str(config_str)?;
 
+    if config.search.as_ref().is_some_and(|q| q.trim().is_empty()) {
+        anyhow::bail!("search query cannot be empty");
+    }
+
+    if config.max_pages == 0 {
+        anyhow::bail!("max_pages needs to be at least 1");
+    }
+
+    for key in config.repo_overrides.keys() {
+        Repo::try_from(key.as_str())
+            .map_err(|err| anyhow::anyhow!("invalid repo override \"{key}\": {err}"))?;
+    }
+
+    let overrides = config
+        .owner_overrides
+        .values()
+        .chain(config.repo_overrides.values());
+    if config.max_merges_per_repo == 0
+        || overrides
+            .map(|o| o.max_merges_per_repo)
+            .any(|max| max == Some(0))
+    {
+        anyhow::bail!("max_merges_per_repo needs to be at least 1");
+    }
+
+    // PRs are listed, freezes are detected, and merges are counted per repo; so these can't vary
+    // by base branch or ecosystem
+    let pr_overrides = config
+        .base_overrides
+        .iter()
+        .map(|(pattern, o)| ("base", pattern.to_string(), o))
+        .chain(
+            config
+                .ecosystem_overrides
+                .iter()
+                .map(|(key, o)| ("ecosystem", key.clone(), o)),
+        );
+    for (kind, key, o) in pr_overrides {
+        if o.base_branch.is_some() {
+            anyhow::bail!("invalid {kind} override \"{key}\": base_branch can't be overridden");
+        }
+        if o.base_branches.is_some() {
+            anyhow::bail!("invalid {kind} override \"{key}\": base_branches can't be overridden");
+        }
+        if o.freeze_label.is_some() {
+            anyhow::bail!("invalid {kind} override \"{key}\": freeze_label can't be overridden");
+        }
+        if o.max_merges_per_repo.is_some() || o.merge_delay.is_some() {
+            anyhow::bail!(
+                "invalid {kind} override \"{key}\": max_merges_per_repo and merge_delay can't be overridden"
+            );
+        }
+    }
+
     Ok(config)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use insta::assert_yaml_snapshot;
+    use insta::{assert_snapshot, assert_yaml_snapshot};
 
     //-------------//
     //  SUCCESSES  //
@@ -78,13 +597,49 @@ repos = [
 ]
 trusted_authors = ["dependabot[bot]"]
 base_branch = "main"
+base_branches = ["main", "release/*"]
 head_pattern = "(dependabot|update)"
 merge_if_blocked = true
+update_behind_branches = true
+request_rebases = true
 merge_if_checks_skipped = true
 merge_if_checks_neutral = true
+required_checks = ["test", "re:lint (.+)"]
+min_checks = 2
+ignored_checks = ["codecov/*", "re:preview-.+"]
+wait_for_pending_checks = { timeout = "15m", poll_interval = "30s" }
+required_labels = ["dependencies"]
+blocking_labels = ["do-not-merge", "mrj:skip"]
+freeze_label = "merge-freeze"
+require_approval = true
+trusted_reviewers = ["octocat"]
+auto_approve = true
+min_pr_age = "72h"
+skip_drafts = false
+max_pr_age = "30d"
+allowed_update_types = ["patch", "minor"]
+allow_packages = ["*"]
+deny_packages = ["openssl*"]
+deny_versions = ["serde@1.0.999"]
+ecosystems = ["cargo", "github_actions"]
+allowed_paths = ["Cargo.toml", "Cargo.lock", ".github/workflows/*"]
+max_changed_files = 5
+max_additions = 500
 merge_type = "squash"
+merge_strategy = "auto-merge"
+commit_title_template = "{{ title }} (#{{ number }})"
+commit_message_template = "Merged-by: mrj"
+max_merges_per_repo = 3
+merge_delay = "2m"
+delete_branch_after_merge = true
+close_superseded = true
 sort_by = "updated"
 sort_direction = "desc"
+max_pages = 5
+
+[[rules]]
+name = "patch bumps"
+rule = 'allow if update_type == "patch"'
 "#;
 
         // WHEN
@@ -93,21 +648,89 @@ sort_direction = "desc"
         // THEN
         assert_yaml_snapshot!(config, @r#"
         repos:
-          - owner: user
-            repo: repo-1
-          - owner: user
-            repo: repo-2
-          - owner: user
-            repo: repo-3
+          - Repo:
+              owner: user
+              repo: repo-1
+          - Repo:
+              owner: user
+              repo: repo-2
+          - Repo:
+              owner: user
+              repo: repo-3
+        search: ~
+        exclude: []
+        skip_archived: true
+        skip_forks: true
         trusted_authors:
           - "dependabot[bot]"
         base_branch: main
+        base_branches:
+          - main
+          - release/*
         merge_if_blocked: true
+        update_behind_branches: true
+        request_rebases: true
         merge_if_checks_skipped: true
         merge_if_checks_neutral: true
+        required_checks:
+          - test
+          - "re:lint (.+)"
+        min_checks: 2
+        ignored_checks:
+          - codecov/*
+          - "re:preview-.+"
+        wait_for_pending_checks:
+          timeout: 15m
+          poll_interval: 30s
+        required_labels:
+          - dependencies
+        blocking_labels:
+          - do-not-merge
+          - "mrj:skip"
+        freeze_label: merge-freeze
+        require_approval: true
+        trusted_reviewers:
+          - octocat
+        auto_approve: true
+        min_pr_age: 72h
+        skip_drafts: false
+        max_pr_age: 30d
+        allowed_update_types:
+          - patch
+          - minor
+        allow_packages:
+          - "*"
+        deny_packages:
+          - openssl*
+        deny_versions:
+          - serde@1.0.999
+        ecosystems:
+          - cargo
+          - github_actions
+        allowed_paths:
+          - Cargo.toml
+          - Cargo.lock
+          - ".github/workflows/*"
+        max_changed_files: 5
+        max_additions: 500
+        rules:
+          - name: patch bumps
+            rule: "allow if update_type == \"patch\""
         merge_type: Squash
+        merge_strategy: auto-merge
+        commit_title_template: "{{ title }} (#{{ number }})"
+        commit_message_template: "Merged-by: mrj"
+        max_merges_per_repo: 3
+        merge_delay: 2m
+        delete_branch_after_merge: true
+        close_superseded: true
         sort_by: updated
         sort_direction: desc
+        max_pages: 5
+        owner: {}
+        repo: {}
+        base: []
+        ecosystem: {}
         "#);
     }
 
@@ -130,24 +753,461 @@ merge_type = "squash"
         // THEN
         assert_yaml_snapshot!(config, @r#"
         repos:
-          - owner: user
-            repo: repo-1
-          - owner: user
-            repo: repo-2
-          - owner: user
-            repo: repo-3
+          - Repo:
+              owner: user
+              repo: repo-1
+          - Repo:
+              owner: user
+              repo: repo-2
+          - Repo:
+              owner: user
+              repo: repo-3
+        search: ~
+        exclude: []
+        skip_archived: true
+        skip_forks: true
+        trusted_authors:
+          - "dependabot[bot]"
+        base_branch: ~
+        base_branches: []
+        merge_if_blocked: false
+        update_behind_branches: false
+        request_rebases: false
+        merge_if_checks_skipped: true
+        merge_if_checks_neutral: false
+        required_checks: []
+        min_checks: 0
+        ignored_checks: []
+        wait_for_pending_checks: ~
+        required_labels: []
+        blocking_labels: []
+        freeze_label: ~
+        require_approval: false
+        trusted_reviewers: []
+        auto_approve: false
+        min_pr_age: ~
+        skip_drafts: true
+        max_pr_age: ~
+        allowed_update_types: ~
+        allow_packages: []
+        deny_packages: []
+        deny_versions: []
+        ecosystems: []
+        allowed_paths: []
+        max_changed_files: ~
+        max_additions: ~
+        rules: []
+        merge_type: Squash
+        merge_strategy: direct
+        commit_title_template: ~
+        commit_message_template: ~
+        max_merges_per_repo: 1
+        merge_delay: ~
+        delete_branch_after_merge: false
+        close_superseded: false
+        sort_by: created
+        sort_direction: asc
+        max_pages: 10
+        owner: {}
+        repo: {}
+        base: []
+        ecosystem: {}
+        "#);
+    }
+
+    #[test]
+    fn parsing_config_with_overrides_works() {
+        // GIVEN
+        let config_str = r#"
+repos = [
+    "user/repo-1",
+    "org/repo-2",
+]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[owner."org"]
+trusted_authors = ["renovate[bot]"]
+base_branch = "develop"
+
+[repo."org/repo-2"]
+merge_type = "rebase"
+merge_if_checks_neutral = true
+"#;
+
+        // WHEN
+        let config = parse_config(config_str).expect("config should've been parsed");
+
+        // THEN
+        assert_yaml_snapshot!(config, @r#"
+        repos:
+          - Repo:
+              owner: user
+              repo: repo-1
+          - Repo:
+              owner: org
+              repo: repo-2
+        search: ~
+        exclude: []
+        skip_archived: true
+        skip_forks: true
         trusted_authors:
           - "dependabot[bot]"
         base_branch: ~
+        base_branches: []
         merge_if_blocked: false
+        update_behind_branches: false
+        request_rebases: false
         merge_if_checks_skipped: true
         merge_if_checks_neutral: false
+        required_checks: []
+        min_checks: 0
+        ignored_checks: []
+        wait_for_pending_checks: ~
+        required_labels: []
+        blocking_labels: []
+        freeze_label: ~
+        require_approval: false
+        trusted_reviewers: []
+        auto_approve: false
+        min_pr_age: ~
+        skip_drafts: true
+        max_pr_age: ~
+        allowed_update_types: ~
+        allow_packages: []
+        deny_packages: []
+        deny_versions: []
+        ecosystems: []
+        allowed_paths: []
+        max_changed_files: ~
+        max_additions: ~
+        rules: []
         merge_type: Squash
+        merge_strategy: direct
+        commit_title_template: ~
+        commit_message_template: ~
+        max_merges_per_repo: 1
+        merge_delay: ~
+        delete_branch_after_merge: false
+        close_superseded: false
         sort_by: created
         sort_direction: asc
+        max_pages: 10
+        owner:
+          org:
+            trusted_authors:
+              - "renovate[bot]"
+            base_branch: develop
+            base_branches: ~
+            merge_if_blocked: ~
+            update_behind_branches: ~
+            request_rebases: ~
+            merge_if_checks_skipped: ~
+            merge_if_checks_neutral: ~
+            required_checks: ~
+            min_checks: ~
+            ignored_checks: ~
+            wait_for_pending_checks: ~
+            required_labels: ~
+            blocking_labels: ~
+            freeze_label: ~
+            require_approval: ~
+            trusted_reviewers: ~
+            auto_approve: ~
+            min_pr_age: ~
+            skip_drafts: ~
+            max_pr_age: ~
+            allowed_update_types: ~
+            allow_packages: ~
+            deny_packages: ~
+            deny_versions: ~
+            ecosystems: ~
+            allowed_paths: ~
+            max_changed_files: ~
+            max_additions: ~
+            rules: ~
+            merge_type: ~
+            merge_strategy: ~
+            commit_title_template: ~
+            commit_message_template: ~
+            max_merges_per_repo: ~
+            merge_delay: ~
+            delete_branch_after_merge: ~
+            close_superseded: ~
+        repo:
+          org/repo-2:
+            trusted_authors: ~
+            base_branch: ~
+            base_branches: ~
+            merge_if_blocked: ~
+            update_behind_branches: ~
+            request_rebases: ~
+            merge_if_checks_skipped: ~
+            merge_if_checks_neutral: true
+            required_checks: ~
+            min_checks: ~
+            ignored_checks: ~
+            wait_for_pending_checks: ~
+            required_labels: ~
+            blocking_labels: ~
+            freeze_label: ~
+            require_approval: ~
+            trusted_reviewers: ~
+            auto_approve: ~
+            min_pr_age: ~
+            skip_drafts: ~
+            max_pr_age: ~
+            allowed_update_types: ~
+            allow_packages: ~
+            deny_packages: ~
+            deny_versions: ~
+            ecosystems: ~
+            allowed_paths: ~
+            max_changed_files: ~
+            max_additions: ~
+            rules: ~
+            merge_type: Rebase
+            merge_strategy: ~
+            commit_title_template: ~
+            commit_message_template: ~
+            max_merges_per_repo: ~
+            merge_delay: ~
+            delete_branch_after_merge: ~
+            close_superseded: ~
+        base: []
+        ecosystem: {}
         "#);
     }
 
+    #[test]
+    fn parsing_config_with_search_query_and_no_repos_works() {
+        // GIVEN
+        let config_str = r#"
+search = "is:pr is:open author:app/dependabot org:user"
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+"#;
+
+        // WHEN
+        let config = parse_config(config_str).expect("config should've been parsed");
+
+        // THEN
+        assert!(config.repos.is_empty());
+        assert_eq!(
+            config.search.as_deref(),
+            Some("is:pr is:open author:app/dependabot org:user")
+        );
+    }
+
+    #[test]
+    fn parsing_config_with_owners_table_works() {
+        // GIVEN
+        let config_str = r#"
+repos = ["org/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[owners."org"]
+merge_type = "rebase"
+"#;
+        let config = parse_config(config_str).expect("config should've been parsed");
+
+        // WHEN
+        let settings = config.settings_for(&Repo {
+            owner: "org".to_string(),
+            repo: "repo-1".to_string(),
+        });
+
+        // THEN
+        assert_yaml_snapshot!(settings.merge_type, @"Rebase");
+    }
+
+    #[test]
+    fn repo_settings_layer_repo_overrides_over_owner_overrides() {
+        // GIVEN
+        let config_str = r#"
+repos = ["org/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+base_branch = "main"
+head_pattern = "dependabot"
+merge_type = "squash"
+
+[owner."org"]
+trusted_authors = ["renovate[bot]"]
+base_branch = "develop"
+head_pattern = "renovate"
+
+[repo."org/repo-1"]
+base_branch = "trunk"
+merge_type = "rebase"
+"#;
+        let config = parse_config(config_str).expect("config should've been parsed");
+
+        // WHEN
+        let settings = config.settings_for(&Repo {
+            owner: "org".to_string(),
+            repo: "repo-1".to_string(),
+        });
+
+        // THEN
+        assert_eq!(settings.trusted_authors, vec!["renovate[bot]".to_string()]);
+        assert_eq!(settings.base_branch.as_deref(), Some("trunk"));
+        assert_eq!(
+            settings.head_pattern.map(|p| p.re.as_str().to_string()),
+            Some("renovate".to_string())
+        );
+        assert!(matches!(settings.merge_type, MergeType::Rebase));
+    }
+
+    #[test]
+    fn repo_settings_fall_back_to_global_settings() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+base_branch = "main"
+merge_if_blocked = true
+merge_type = "squash"
+
+[owner."org"]
+base_branch = "develop"
+merge_if_blocked = false
+
+[repo."org/repo-1"]
+merge_type = "rebase"
+"#;
+        let config = parse_config(config_str).expect("config should've been parsed");
+
+        // WHEN
+        let settings = config.settings_for(&Repo {
+            owner: "user".to_string(),
+            repo: "repo-1".to_string(),
+        });
+
+        // THEN
+        assert_eq!(
+            settings.trusted_authors,
+            vec!["dependabot[bot]".to_string()]
+        );
+        assert_eq!(settings.base_branch.as_deref(), Some("main"));
+        assert!(settings.merge_if_blocked);
+        assert!(matches!(settings.merge_type, MergeType::Squash));
+    }
+
+    #[test]
+    fn ecosystem_settings_layer_over_repo_settings() {
+        // GIVEN
+        let config_str = r#"
+repos = ["org/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+ecosystems = ["cargo", "npm_and_yarn"]
+merge_type = "squash"
+
+[repo."org/repo-1"]
+merge_if_checks_skipped = false
+merge_type = "rebase"
+
+[ecosystem."npm_and_yarn"]
+allowed_update_types = ["patch"]
+merge_type = "merge"
+"#;
+        let config = parse_config(config_str).expect("config should've been parsed");
+        let repo = Repo {
+            owner: "org".to_string(),
+            repo: "repo-1".to_string(),
+        };
+
+        // WHEN
+        let npm = config.settings_for_pr(&repo, "main", Some("npm_and_yarn"));
+        let cargo = config.settings_for_pr(&repo, "main", Some("cargo"));
+
+        // THEN
+        assert_eq!(npm.allowed_update_types, Some(vec![UpdateType::Patch]));
+        assert!(!npm.merge_if_checks_skipped);
+        assert!(matches!(npm.merge_type, MergeType::Merge));
+        assert_eq!(cargo.allowed_update_types, None);
+        assert!(matches!(cargo.merge_type, MergeType::Rebase));
+    }
+
+    #[test]
+    fn base_settings_layer_between_repo_and_ecosystem_settings() {
+        // GIVEN
+        let config_str = r#"
+repos = ["org/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+base_branches = ["main", "release/*"]
+merge_type = "squash"
+
+[repo."org/repo-1"]
+merge_type = "rebase"
+
+[base."release/*"]
+allowed_update_types = ["patch"]
+merge_type = "merge"
+
+[ecosystem."cargo"]
+merge_type = "squash"
+"#;
+        let config = parse_config(config_str).expect("config should've been parsed");
+        let repo = Repo {
+            owner: "org".to_string(),
+            repo: "repo-1".to_string(),
+        };
+
+        // WHEN
+        let main = config.settings_for_pr(&repo, "main", None);
+        let release = config.settings_for_pr(&repo, "release/1.x", None);
+        let release_cargo = config.settings_for_pr(&repo, "release/1.x", Some("cargo"));
+
+        // THEN
+        assert_eq!(main.allowed_update_types, None);
+        assert!(matches!(main.merge_type, MergeType::Rebase));
+        assert_eq!(release.allowed_update_types, Some(vec![UpdateType::Patch]));
+        assert!(matches!(release.merge_type, MergeType::Merge));
+        assert_eq!(
+            release_cargo.allowed_update_types,
+            Some(vec![UpdateType::Patch])
+        );
+        assert!(matches!(release_cargo.merge_type, MergeType::Squash));
+        assert_eq!(
+            release
+                .base_branches
+                .iter()
+                .map(|b| b.to_string())
+                .collect::<Vec<_>>(),
+            vec!["main".to_string(), "release/*".to_string()]
+        );
+    }
+
+    #[test]
+    fn base_overrides_are_applied_in_the_order_they_are_declared_in() {
+        // GIVEN
+        let config_str = r#"
+repos = ["org/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[base."release/1.x"]
+allowed_update_types = ["patch"]
+merge_type = "merge"
+
+[base."release/*"]
+merge_type = "rebase"
+"#;
+        let config = parse_config(config_str).expect("config should've been parsed");
+        let repo = Repo {
+            owner: "org".to_string(),
+            repo: "repo-1".to_string(),
+        };
+
+        // WHEN
+        let release = config.settings_for_pr(&repo, "release/1.x", None);
+
+        // THEN
+        assert_eq!(release.allowed_update_types, Some(vec![UpdateType::Patch]));
+        assert!(matches!(release.merge_type, MergeType::Rebase));
+    }
+
     //-------------//
     //  FAILURES   //
     //-------------//
@@ -190,6 +1250,375 @@ merge_type = "squash"
         let _ = parse_config(config_str).expect_err("config shouldn't have been parsed");
     }
 
+    #[test]
+    fn parsing_invalid_repo_override_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[repo."repo-1"]
+merge_type = "rebase"
+"#;
+
+        // WHEN
+        // THEN
+        let _ = parse_config(config_str).expect_err("config shouldn't have been parsed");
+    }
+
+    #[test]
+    fn parsing_empty_search_query_fails() {
+        // GIVEN
+        let config_str = r#"
+search = " "
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert_eq!(err.to_string(), "search query cannot be empty");
+    }
+
+    #[test]
+    fn parsing_config_with_zero_max_pages_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+max_pages = 0
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert_eq!(err.to_string(), "max_pages needs to be at least 1");
+    }
+
+    #[test]
+    fn parsing_config_with_zero_max_merges_per_repo_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[repo."user/repo-1"]
+max_merges_per_repo = 0
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert_eq!(
+            err.to_string(),
+            "max_merges_per_repo needs to be at least 1"
+        );
+    }
+
+    #[test]
+    fn parsing_ecosystem_override_with_merge_delay_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[ecosystem."cargo"]
+merge_delay = "5m"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert_eq!(
+            err.to_string(),
+            r#"invalid ecosystem override "cargo": max_merges_per_repo and merge_delay can't be overridden"#
+        );
+    }
+
+    #[test]
+    fn parsing_ecosystem_override_with_base_branch_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[ecosystem."cargo"]
+base_branch = "develop"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert_eq!(
+            err.to_string(),
+            r#"invalid ecosystem override "cargo": base_branch can't be overridden"#
+        );
+    }
+
+    #[test]
+    fn parsing_ecosystem_override_with_freeze_label_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[ecosystem."cargo"]
+freeze_label = "merge-freeze"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert_eq!(
+            err.to_string(),
+            r#"invalid ecosystem override "cargo": freeze_label can't be overridden"#
+        );
+    }
+
+    #[test]
+    fn parsing_base_override_with_invalid_pattern_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[base."release/[1.x"]
+allowed_update_types = ["patch"]
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert_snapshot!(err.to_string(), @r#"
+        TOML parse error at line 6, column 2
+          |
+        6 | [base."release/[1.x"]
+          |  ^^^^
+        invalid base override "release/[1.x": error parsing glob 'release/[1.x': unclosed character class; missing ']'
+        "#);
+    }
+
+    #[test]
+    fn parsing_base_override_with_base_branches_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[base."release/*"]
+base_branches = ["release/1.x"]
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert_eq!(
+            err.to_string(),
+            r#"invalid base override "release/*": base_branches can't be overridden"#
+        );
+    }
+
+    #[test]
+    fn parsing_base_override_with_max_merges_per_repo_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[base."release/*"]
+max_merges_per_repo = 2
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert_eq!(
+            err.to_string(),
+            r#"invalid base override "release/*": max_merges_per_repo and merge_delay can't be overridden"#
+        );
+    }
+
+    #[test]
+    fn parsing_override_with_unknown_setting_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[owner."user"]
+sort_by = "updated"
+"#;
+
+        // WHEN
+        // THEN
+        let _ = parse_config(config_str).expect_err("config shouldn't have been parsed");
+    }
+
+    #[test]
+    fn parsing_config_with_unknown_key_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[overrides."user"]
+merge_type = "rebase"
+"#;
+
+        // WHEN
+        // THEN
+        let _ = parse_config(config_str).expect_err("config shouldn't have been parsed");
+    }
+
+    #[test]
+    fn parsing_repos_table_fails_with_a_hint() {
+        // GIVEN
+        let config_str = r#"
+search = "is:pr is:open org:user"
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+
+[repos."user/repo-1"]
+merge_type = "rebase"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert_snapshot!(err.to_string(), @r#"
+        TOML parse error at line 6, column 2
+          |
+        6 | [repos."user/repo-1"]
+          |  ^^^^^
+        repos needs to be a list of repos; settings for a single repo go in a [repo."<owner>/<repo>"] table
+        "#);
+    }
+
+    #[test]
+    fn parsing_invalid_update_type_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+allowed_update_types = ["patch", "tiny"]
+merge_type = "squash"
+"#;
+
+        // WHEN
+        // THEN
+        let _ = parse_config(config_str).expect_err("config shouldn't have been parsed");
+    }
+
+    #[test]
+    fn parsing_invalid_required_check_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+required_checks = ["test", "lint [unclosed"]
+merge_type = "squash"
+"#;
+
+        // WHEN
+        // THEN
+        let _ = parse_config(config_str).expect_err("config shouldn't have been parsed");
+    }
+
+    #[test]
+    fn parsing_invalid_wait_for_pending_checks_fails() {
+        // GIVEN
+        let configs = [
+            r#"wait_for_pending_checks = { timeout = "15 minutes" }"#,
+            r#"wait_for_pending_checks = { timeout = "15m", poll_interval = "0s" }"#,
+            r#"wait_for_pending_checks = { poll_interval = "30s" }"#,
+        ];
+
+        // WHEN
+        // THEN
+        for extra in configs {
+            let config_str = format!(
+                r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+{extra}
+"#
+            );
+            let _ = parse_config(&config_str).expect_err("config shouldn't have been parsed");
+        }
+    }
+
+    #[test]
+    fn parsing_invalid_package_version_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+deny_versions = ["serde"]
+merge_type = "squash"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert!(
+            err.to_string()
+                .contains(r#"expected a value in the form "package@version""#)
+        );
+    }
+
+    #[test]
+    fn parsing_invalid_commit_template_fails() {
+        // GIVEN
+        let config_str = r#"
+repos = ["user/repo-1"]
+trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+commit_title_template = "{{ title"
+"#;
+
+        // WHEN
+        let result = parse_config(config_str);
+
+        // THEN
+        let err = result.expect_err("config shouldn't have been parsed");
+        assert!(err.to_string().contains("expected a valid tera template"));
+    }
+
     #[test]
     fn parsing_invalid_merge_type_fails() {
         // GIVEN
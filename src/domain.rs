@@ -1,14 +1,18 @@
+use crate::upgrade::{UpdateBot, UpdateType, Upgrade, Version, detect_ecosystem};
 use chrono::{DateTime, Utc};
+use globset::{GlobBuilder, GlobMatcher};
 use octocrab::models::pulls::PullRequest;
 use octocrab::params::Direction;
 use octocrab::params::pulls::{MergeMethod, Sort};
 use regex::Regex;
 use serde::de::{self, Visitor};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt::{self, Display};
 use std::path::PathBuf;
+use std::time::Duration;
+use tera::{Context as TeraContext, Tera};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(test, derive(serde::Serialize))]
 pub enum MergeType {
     Merge,
@@ -24,6 +28,42 @@ impl MergeType {
             MergeType::Rebase => MergeMethod::Rebase,
         }
     }
+
+    /// The corresponding value of GitHub's GraphQL `PullRequestMergeMethod` enum.
+    pub fn graphql_merge_method(&self) -> &'static str {
+        match self {
+            MergeType::Merge => "MERGE",
+            MergeType::Squash => "SQUASH",
+            MergeType::Rebase => "REBASE",
+        }
+    }
+}
+
+/// How qualifying PRs get merged: by mrj itself, or by GitHub, via native auto-merge or a merge
+/// queue, once the PR's requirements (eg. pending checks) are met.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(rename_all = "kebab-case")]
+pub enum MergeStrategy {
+    Direct,
+    AutoMerge,
+    MergeQueue,
+}
+
+impl MergeStrategy {
+    pub fn is_direct(&self) -> bool {
+        matches!(self, MergeStrategy::Direct)
+    }
+}
+
+impl Display for MergeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeStrategy::Direct => write!(f, "direct"),
+            MergeStrategy::AutoMerge => write!(f, "auto-merge"),
+            MergeStrategy::MergeQueue => write!(f, "merge-queue"),
+        }
+    }
 }
 
 impl<'de> Deserialize<'de> for MergeType {
@@ -57,7 +97,7 @@ impl<'de> Deserialize<'de> for MergeType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(test, derive(serde::Serialize))]
 pub struct Repo {
     pub owner: String,
@@ -116,37 +156,572 @@ impl<'de> Deserialize<'de> for Repo {
     }
 }
 
-#[derive(Debug)]
+/// An entry in the "repos" list of the config; either a single repo, or a way to discover repos
+/// of an owner at the start of a run.
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub enum RepoSelector {
+    Repo(Repo),
+    Pattern { owner: String, pattern: Glob },
+    Topic { owner: String, topic: String },
+}
+
+impl RepoSelector {
+    pub fn owner(&self) -> &str {
+        match self {
+            RepoSelector::Repo(r) => &r.owner,
+            RepoSelector::Pattern { owner, .. } => owner,
+            RepoSelector::Topic { owner, .. } => owner,
+        }
+    }
+}
+
+impl Display for RepoSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepoSelector::Repo(r) => write!(f, "{r}"),
+            RepoSelector::Pattern { owner, pattern } => write!(f, "{owner}/{pattern}"),
+            RepoSelector::Topic { owner, topic } => write!(f, "{owner} (topic: {topic})"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RepoSelector {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct TopicSelector {
+            owner: String,
+            topic: String,
+        }
+
+        struct RepoSelectorVisitor;
+
+        impl<'de> Visitor<'de> for RepoSelectorVisitor {
+            type Value = RepoSelector;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(
+                    r#"a value in the form "owner/repo" or "owner/<glob>", or a table in the form { owner = "owner", topic = "topic" }"#,
+                )
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let Some((owner, repo)) = value.split_once("/") else {
+                    return Err(de::Error::invalid_value(de::Unexpected::Str(value), &self));
+                };
+
+                if !repo.contains(['*', '?', '[', '{']) {
+                    return Ok(RepoSelector::Repo(Repo {
+                        owner: owner.to_string(),
+                        repo: repo.to_string(),
+                    }));
+                }
+
+                match Glob::new(repo) {
+                    Ok(pattern) => Ok(RepoSelector::Pattern {
+                        owner: owner.to_string(),
+                        pattern,
+                    }),
+                    Err(_) => Err(de::Error::invalid_value(de::Unexpected::Str(value), &self)),
+                }
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let TopicSelector { owner, topic } =
+                    TopicSelector::deserialize(de::value::MapAccessDeserializer::new(map))?;
+
+                Ok(RepoSelector::Topic { owner, topic })
+            }
+        }
+
+        deserializer.deserialize_any(RepoSelectorVisitor)
+    }
+}
+
+/// A glob pattern; "*" doesn't match across "/", "**" does.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    raw: String,
+    matcher: GlobMatcher,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Result<Self, globset::Error> {
+        let matcher = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()?
+            .compile_matcher();
+
+        Ok(Self {
+            raw: pattern.to_string(),
+            matcher,
+        })
+    }
+
+    pub fn is_match(&self, value: &str) -> bool {
+        self.matcher.is_match(value)
+    }
+}
+
+impl Display for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for Glob {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct GlobVisitor;
+
+        impl Visitor<'_> for GlobVisitor {
+            type Value = Glob;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid glob pattern")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Glob::new(value)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_str(GlobVisitor)
+    }
+}
+
+#[cfg(test)]
+impl serde::Serialize for Glob {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+/// A version of a package, in the form "<package>@<version>"; the package name is a glob.
+#[derive(Debug, Clone)]
+pub struct PackageVersion {
+    pub package: Glob,
+    pub version: Version,
+}
+
+impl PackageVersion {
+    pub fn is_match(&self, package: &str, version: &Version) -> bool {
+        self.package.is_match(package) && self.version.same_as(version)
+    }
+}
+
+impl Display for PackageVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.package, self.version)
+    }
+}
+
+impl<'de> Deserialize<'de> for PackageVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PackageVersionVisitor;
+
+        impl Visitor<'_> for PackageVersionVisitor {
+            type Value = PackageVersion;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(r#"a value in the form "package@version""#)
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                // scoped npm packages start with an "@"
+                let parsed = value
+                    .rsplit_once('@')
+                    .filter(|(package, _)| !package.is_empty())
+                    .and_then(|(package, version)| {
+                        Some(PackageVersion {
+                            package: Glob::new(package).ok()?,
+                            version: Version::parse(version)?,
+                        })
+                    });
+
+                parsed.ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_str(PackageVersionVisitor)
+    }
+}
+
+#[cfg(test)]
+impl serde::Serialize for PackageVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct HeadPattern {
     pub re: Regex,
 }
 
-impl<'de> Deserialize<'de> for HeadPattern {
+impl<'de> Deserialize<'de> for HeadPattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct HeadPatternVisitor;
+
+        impl Visitor<'_> for HeadPatternVisitor {
+            type Value = HeadPattern;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(r#"a valid regex"#)
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match Regex::new(value) {
+                    Ok(re) => Ok(HeadPattern { re }),
+                    _ => Err(de::Error::invalid_value(de::Unexpected::Str(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(HeadPatternVisitor)
+    }
+}
+
+/// The name of a check run; a glob by default (so a plain name matches exactly), or a regex
+/// against the whole name when prefixed with "re:".
+#[derive(Debug, Clone)]
+pub struct CheckPattern {
+    pub raw: String,
+    matcher: CheckMatcher,
+}
+
+#[derive(Debug, Clone)]
+enum CheckMatcher {
+    Glob(Glob),
+    Regex(Regex),
+}
+
+impl CheckPattern {
+    pub fn parse(value: &str) -> Option<Self> {
+        let matcher = match value.strip_prefix("re:") {
+            Some(re) if !re.is_empty() => {
+                CheckMatcher::Regex(Regex::new(&format!("^(?:{re})$")).ok()?)
+            }
+            Some(_) => return None,
+            None if value.is_empty() => return None,
+            None => CheckMatcher::Glob(Glob::new(value).ok()?),
+        };
+
+        Some(Self {
+            raw: value.to_string(),
+            matcher,
+        })
+    }
+
+    pub fn is_match(&self, name: &str) -> bool {
+        match &self.matcher {
+            CheckMatcher::Glob(glob) => glob.is_match(name),
+            CheckMatcher::Regex(re) => re.is_match(name),
+        }
+    }
+}
+
+impl Display for CheckPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for CheckPattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct CheckPatternVisitor;
+
+        impl Visitor<'_> for CheckPatternVisitor {
+            type Value = CheckPattern;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(
+                    r#"a check name, a valid glob, or a valid regex prefixed with "re:""#,
+                )
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                CheckPattern::parse(value)
+                    .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_str(CheckPatternVisitor)
+    }
+}
+
+#[cfg(test)]
+impl serde::Serialize for CheckPattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+/// A Tera template for the title or the message of the commit created when merging a PR; see
+/// `CommitContext` for what it can refer to.
+#[derive(Debug, Clone)]
+pub struct CommitTemplate {
+    raw: String,
+}
+
+impl CommitTemplate {
+    pub fn parse(value: &str) -> Option<Self> {
+        Tera::default().add_raw_template("commit", value).ok()?;
+
+        Some(Self {
+            raw: value.to_string(),
+        })
+    }
+
+    pub fn render(&self, context: &CommitContext) -> anyhow::Result<String> {
+        let tera_ctx = TeraContext::from_serialize(context)?;
+        let rendered = Tera::one_off(&self.raw, &tera_ctx, false)?;
+
+        Ok(rendered.trim().to_string())
+    }
+}
+
+impl Display for CommitTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for CommitTemplate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct CommitTemplateVisitor;
+
+        impl Visitor<'_> for CommitTemplateVisitor {
+            type Value = CommitTemplate;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid tera template")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                CommitTemplate::parse(value)
+                    .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_str(CommitTemplateVisitor)
+    }
+}
+
+#[cfg(test)]
+impl serde::Serialize for CommitTemplate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+/// What commit templates can refer to.
+#[derive(Debug, Serialize)]
+pub struct CommitContext {
+    pub number: u64,
+    pub title: String,
+    pub body: String,
+    pub author: String,
+    pub head_ref: String,
+    pub ecosystem: Option<String>,
+    /// `None` if the PR isn't recognised as an upgrade.
+    pub upgrade: Option<CommitUpgrade>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommitUpgrade {
+    pub package: String,
+    pub from: Option<String>,
+    pub to: String,
+    pub update_type: Option<String>,
+}
+
+impl From<&Upgrade> for CommitUpgrade {
+    fn from(upgrade: &Upgrade) -> Self {
+        Self {
+            package: upgrade.package.clone(),
+            from: upgrade.from.as_ref().map(|v| v.to_string()),
+            to: upgrade.to.to_string(),
+            update_type: upgrade.update_type().map(|t| t.to_string()),
+        }
+    }
+}
+
+/// A non-zero duration, written as a sequence of amounts with units, eg. "30s", "15m", "1h30m";
+/// the supported units are "s", "m", "h", "d", and "w".
+#[derive(Debug, Clone)]
+pub struct HumanDuration {
+    raw: String,
+    pub duration: Duration,
+}
+
+impl HumanDuration {
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut total: u64 = 0;
+        let mut amount: Option<u64> = None;
+
+        for c in value.trim().chars() {
+            if let Some(digit) = c.to_digit(10) {
+                amount = Some(
+                    amount
+                        .unwrap_or_default()
+                        .checked_mul(10)?
+                        .checked_add(u64::from(digit))?,
+                );
+                continue;
+            }
+
+            let unit_secs = match c {
+                's' => 1,
+                'm' => 60,
+                'h' => 60 * 60,
+                'd' => 24 * 60 * 60,
+                'w' => 7 * 24 * 60 * 60,
+                _ => return None,
+            };
+            total = total.checked_add(amount.take()?.checked_mul(unit_secs)?)?;
+        }
+
+        if amount.is_some() || total == 0 {
+            return None;
+        }
+
+        Some(Self {
+            raw: value.trim().to_string(),
+            duration: Duration::from_secs(total),
+        })
+    }
+}
+
+impl From<Duration> for HumanDuration {
+    /// Writes the duration in days, hours, and minutes, rounding up to the minute.
+    fn from(duration: Duration) -> Self {
+        let minutes = duration.as_secs().div_ceil(60);
+        let (days, hours, minutes) = (minutes / 1440, (minutes % 1440) / 60, minutes % 60);
+
+        let mut raw = String::new();
+        for (amount, unit) in [(days, 'd'), (hours, 'h'), (minutes, 'm')] {
+            if amount > 0 {
+                raw.push_str(&format!("{amount}{unit}"));
+            }
+        }
+        if raw.is_empty() {
+            raw.push_str("0m");
+        }
+
+        Self { raw, duration }
+    }
+}
+
+impl Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for HumanDuration {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct HeadPatternVisitor;
+        struct HumanDurationVisitor;
 
-        impl Visitor<'_> for HeadPatternVisitor {
-            type Value = HeadPattern;
+        impl Visitor<'_> for HumanDurationVisitor {
+            type Value = HumanDuration;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str(r#"a valid regex"#)
+                formatter.write_str(r#"a non-zero duration, eg. "30s", "15m", or "1h30m""#)
             }
 
             fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                match Regex::new(value) {
-                    Ok(re) => Ok(HeadPattern { re }),
-                    _ => Err(de::Error::invalid_value(de::Unexpected::Str(value), &self)),
-                }
+                HumanDuration::parse(value)
+                    .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(value), &self))
             }
         }
 
-        deserializer.deserialize_str(HeadPatternVisitor)
+        deserializer.deserialize_str(HumanDurationVisitor)
+    }
+}
+
+#[cfg(test)]
+impl serde::Serialize for HumanDuration {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+/// How long to keep polling check runs that haven't concluded yet, before giving up on a PR.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct WaitForPendingChecks {
+    pub timeout: HumanDuration,
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval: HumanDuration,
+}
+
+fn default_poll_interval() -> HumanDuration {
+    HumanDuration {
+        raw: "30s".to_string(),
+        duration: Duration::from_secs(30),
     }
 }
 
@@ -185,6 +760,17 @@ impl GhApiQueryParam<Sort> for SortBy {
     }
 }
 
+impl SortBy {
+    /// The closest sort option offered by GitHub's search API.
+    pub fn search_sort(&self) -> &'static str {
+        match self {
+            SortBy::Created | SortBy::LongRunning => "created",
+            SortBy::Updated => "updated",
+            SortBy::Popularity => "comments",
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, PartialEq)]
 #[cfg_attr(test, derive(serde::Serialize))]
 pub enum SortDirection {
@@ -210,6 +796,15 @@ impl GhApiQueryParam<Direction> for SortDirection {
     }
 }
 
+impl SortDirection {
+    pub fn search_order(&self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "asc",
+            SortDirection::Descending => "desc",
+        }
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(test, derive(serde::Serialize))]
 pub enum RepoResult {
@@ -230,6 +825,7 @@ impl RepoResult {
 
 #[derive(Debug)]
 pub struct RunMergeResults {
+    pub selected_repos: Vec<Repo>,
     pub results: Vec<RepoResult>,
     pub summary: RunSummary,
     pub started_at: DateTime<Utc>,
@@ -322,6 +918,8 @@ pub struct PRCheck<S: PRCheckState> {
     pub url: String,
     pub pr_created_at: Option<DateTime<Utc>>,
     pub pr_updated_at: Option<DateTime<Utc>>,
+    pub upgrade: Option<Upgrade>,
+    pub ecosystem: Option<String>,
     pub qualifications: Vec<Qualification>,
     pub state: S,
 }
@@ -330,18 +928,28 @@ pub struct PRCheck<S: PRCheckState> {
 #[cfg_attr(test, derive(serde::Serialize))]
 pub enum MergeResult {
     Qualified(PRCheck<PRCheckFinished>),
+    BranchUpdated(PRCheck<PRBranchUpdated>),
+    Enqueued(PRCheck<PREnqueued>),
     Disqualified(PRCheck<PRDisqualified>),
     Errored(PRCheck<PRCheckErrored>),
 }
 
 impl MergeResult {
-    pub fn no_failure(&self) -> bool {
-        matches!(self, MergeResult::Qualified(_))
+    /// Whether the PR counts towards a repo's maximum number of merges per run; PRs that GitHub
+    /// had already been handed over before the run don't.
+    pub fn takes_merge_slot(&self) -> bool {
+        match self {
+            MergeResult::Qualified(_) => true,
+            MergeResult::Enqueued(r) => !r.state.already_handed_over,
+            _ => false,
+        }
     }
 
     pub fn pr_number(&self) -> u64 {
         match self {
             MergeResult::Qualified(r) => r.number,
+            MergeResult::BranchUpdated(r) => r.number,
+            MergeResult::Enqueued(r) => r.number,
             MergeResult::Disqualified(r) => r.number,
             MergeResult::Errored(r) => r.number,
         }
@@ -350,6 +958,8 @@ impl MergeResult {
     pub fn pr_title(&self) -> &str {
         match self {
             MergeResult::Qualified(r) => &r.title,
+            MergeResult::BranchUpdated(r) => &r.title,
+            MergeResult::Enqueued(r) => &r.title,
             MergeResult::Disqualified(r) => &r.title,
             MergeResult::Errored(r) => &r.title,
         }
@@ -358,6 +968,8 @@ impl MergeResult {
     pub fn pr_url(&self) -> &str {
         match self {
             MergeResult::Qualified(r) => &r.url,
+            MergeResult::BranchUpdated(r) => &r.url,
+            MergeResult::Enqueued(r) => &r.url,
             MergeResult::Disqualified(r) => &r.url,
             MergeResult::Errored(r) => &r.url,
         }
@@ -366,6 +978,8 @@ impl MergeResult {
     pub fn pr_created_at(&self) -> Option<DateTime<Utc>> {
         match self {
             MergeResult::Qualified(r) => r.pr_created_at,
+            MergeResult::BranchUpdated(r) => r.pr_created_at,
+            MergeResult::Enqueued(r) => r.pr_created_at,
             MergeResult::Disqualified(r) => r.pr_created_at,
             MergeResult::Errored(r) => r.pr_created_at,
         }
@@ -374,6 +988,8 @@ impl MergeResult {
     pub fn pr_updated_at(&self) -> Option<DateTime<Utc>> {
         match self {
             MergeResult::Qualified(r) => r.pr_updated_at,
+            MergeResult::BranchUpdated(r) => r.pr_updated_at,
+            MergeResult::Enqueued(r) => r.pr_updated_at,
             MergeResult::Disqualified(r) => r.pr_updated_at,
             MergeResult::Errored(r) => r.pr_updated_at,
         }
@@ -382,6 +998,8 @@ impl MergeResult {
     pub fn qualifications(&self) -> &Vec<Qualification> {
         match self {
             MergeResult::Qualified(r) => &r.qualifications,
+            MergeResult::BranchUpdated(r) => &r.qualifications,
+            MergeResult::Enqueued(r) => &r.qualifications,
             MergeResult::Disqualified(r) => &r.qualifications,
             MergeResult::Errored(r) => &r.qualifications,
         }
@@ -428,14 +1046,69 @@ impl serde::Serialize for PRCheckErrored {
     }
 }
 
+/// The PR qualified for merging; `head_sha` is the commit it was evaluated at, and
+/// `merge_commit_sha` the commit created by merging it (`None` during dry runs).
 #[derive(Debug)]
 #[cfg_attr(test, derive(serde::Serialize))]
-pub struct PRCheckFinished;
+pub struct PRCheckFinished {
+    pub head_sha: String,
+    pub merge_commit_sha: Option<String>,
+    pub post_merge: Vec<PostMergeOutcome>,
+}
 impl private::Sealed for PRCheckFinished {}
 impl PRCheckState for PRCheckFinished {}
 
+/// Something mrj does after merging a PR.
+#[derive(Debug)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub enum PostMergeAction {
+    /// The PR's head branch gets deleted.
+    DeleteBranch(String),
+    /// The PR with this number gets closed, as the merged PR supersedes it: both upgrade the same
+    /// package, and the merged PR is the newer one.
+    CloseSuperseded(u64),
+}
+
+#[derive(Debug)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct PostMergeOutcome {
+    pub action: PostMergeAction,
+    /// Why the action failed, if it did; a failed action doesn't undo the merge.
+    pub error: Option<String>,
+}
+
+impl PRCheck<PRCheckFinished> {
+    pub fn add_post_merge_outcome(&mut self, action: PostMergeAction, error: Option<String>) {
+        self.state
+            .post_merge
+            .push(PostMergeOutcome { action, error });
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct PRBranchUpdated;
+impl private::Sealed for PRBranchUpdated {}
+impl PRCheckState for PRBranchUpdated {}
+
+/// The PR qualified, and was handed over to GitHub to merge once its requirements are met, via
+/// the strategy (either auto-merge or a merge queue); `head_sha` is the commit it was evaluated at.
+/// `already_handed_over` is set when auto-merge had been enabled, or the PR queued, before the run,
+/// in which case it's left as is.
+#[derive(Debug)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct PREnqueued {
+    pub strategy: MergeStrategy,
+    pub head_sha: String,
+    pub already_handed_over: bool,
+}
+impl private::Sealed for PREnqueued {}
+impl PRCheckState for PREnqueued {}
+
 impl From<&PullRequest> for PRCheck<PRCheckInProgress> {
     fn from(pr: &PullRequest) -> Self {
+        let title = pr.title.as_deref().unwrap_or_default();
+
         Self {
             number: pr.number,
             title: pr.title.clone().unwrap_or_default(),
@@ -446,6 +1119,8 @@ impl From<&PullRequest> for PRCheck<PRCheckInProgress> {
                 .unwrap_or_default(),
             pr_created_at: pr.created_at,
             pr_updated_at: pr.updated_at,
+            upgrade: Upgrade::parse(title, pr.body.as_deref(), &pr.head.ref_field),
+            ecosystem: detect_ecosystem(title, pr.body.as_deref(), &pr.head.ref_field),
             qualifications: vec![],
             state: PRCheckInProgress,
         }
@@ -464,6 +1139,8 @@ impl PRCheck<PRCheckInProgress> {
             url: self.url,
             pr_created_at: self.pr_created_at,
             pr_updated_at: self.pr_updated_at,
+            upgrade: self.upgrade,
+            ecosystem: self.ecosystem,
             qualifications: self.qualifications,
             state: PRDisqualified(dq),
         }
@@ -476,20 +1153,69 @@ impl PRCheck<PRCheckInProgress> {
             url: self.url,
             pr_created_at: self.pr_created_at,
             pr_updated_at: self.pr_updated_at,
+            upgrade: self.upgrade,
+            ecosystem: self.ecosystem,
             qualifications: self.qualifications,
             state: PRCheckErrored(error),
         }
     }
 
-    pub fn finish(self) -> PRCheck<PRCheckFinished> {
+    pub fn finish(
+        self,
+        head_sha: String,
+        merge_commit_sha: Option<String>,
+    ) -> PRCheck<PRCheckFinished> {
+        PRCheck {
+            number: self.number,
+            title: self.title,
+            url: self.url,
+            pr_created_at: self.pr_created_at,
+            pr_updated_at: self.pr_updated_at,
+            upgrade: self.upgrade,
+            ecosystem: self.ecosystem,
+            qualifications: self.qualifications,
+            state: PRCheckFinished {
+                head_sha,
+                merge_commit_sha,
+                post_merge: vec![],
+            },
+        }
+    }
+
+    pub fn mark_branch_updated(self) -> PRCheck<PRBranchUpdated> {
+        PRCheck {
+            number: self.number,
+            title: self.title,
+            url: self.url,
+            pr_created_at: self.pr_created_at,
+            pr_updated_at: self.pr_updated_at,
+            upgrade: self.upgrade,
+            ecosystem: self.ecosystem,
+            qualifications: self.qualifications,
+            state: PRBranchUpdated,
+        }
+    }
+
+    pub fn enqueue(
+        self,
+        strategy: MergeStrategy,
+        head_sha: String,
+        already_handed_over: bool,
+    ) -> PRCheck<PREnqueued> {
         PRCheck {
             number: self.number,
             title: self.title,
             url: self.url,
             pr_created_at: self.pr_created_at,
             pr_updated_at: self.pr_updated_at,
+            upgrade: self.upgrade,
+            ecosystem: self.ecosystem,
             qualifications: self.qualifications,
-            state: PRCheckFinished,
+            state: PREnqueued {
+                strategy,
+                head_sha,
+                already_handed_over,
+            },
         }
     }
 }
@@ -514,6 +1240,8 @@ pub struct RunSummary {
     pub disqualifications: Vec<RunDisqualification>,
     pub num_errors: u16,
     pub prs_merged: Vec<MergedPR>,
+    pub branches_updated: Vec<MergedPR>,
+    pub prs_enqueued: Vec<MergedPR>,
 }
 
 impl RunSummary {
@@ -521,6 +1249,8 @@ impl RunSummary {
         let mut num_errors = 0;
         let mut disqualifications = vec![];
         let mut prs_merged = vec![];
+        let mut branches_updated = vec![];
+        let mut prs_enqueued = vec![];
 
         for result in results {
             match result {
@@ -538,6 +1268,22 @@ impl RunSummary {
                                     });
                                 }
                             }
+                            MergeResult::BranchUpdated(pr_check) => {
+                                if did_execute {
+                                    branches_updated.push(MergedPR {
+                                        repo: result.name(),
+                                        title: pr_check.title.clone(),
+                                    });
+                                }
+                            }
+                            MergeResult::Enqueued(pr_check) => {
+                                if did_execute && !pr_check.state.already_handed_over {
+                                    prs_enqueued.push(MergedPR {
+                                        repo: result.name(),
+                                        title: pr_check.title.clone(),
+                                    });
+                                }
+                            }
                             MergeResult::Disqualified(pr_check) => {
                                 disqualifications.push(RunDisqualification {
                                     pr_url: pr_check.url.clone(),
@@ -557,6 +1303,8 @@ impl RunSummary {
             disqualifications,
             num_errors,
             prs_merged,
+            branches_updated,
+            prs_enqueued,
         }
     }
 }
@@ -564,10 +1312,43 @@ impl RunSummary {
 #[derive(Debug)]
 #[cfg_attr(test, derive(serde::Serialize))]
 pub enum Qualification {
+    Base(String),
     Head(String),
     Author(String),
-    Check { name: String, conclusion: String },
+    Check {
+        name: String,
+        conclusion: String,
+    },
     State(String),
+    UpdateType {
+        package: String,
+        update_type: UpdateType,
+    },
+    Package(String),
+    Ecosystem(String),
+    /// The check was ignored as per the config, regardless of its conclusion.
+    IgnoredCheck {
+        name: String,
+        conclusion: Option<String>,
+    },
+    /// The PR has one of the required labels.
+    Label(String),
+    /// The PR has an approving review from the reviewer.
+    Approval(String),
+    /// The PR has no approving review yet, and will be approved by mrj before being merged.
+    AutoApproval,
+    /// The PR is the nth one to be merged in its repo during this run.
+    MergeSlot {
+        slot: usize,
+        max: usize,
+    },
+    /// All of the files the PR changes (these many) match the allowed paths.
+    AllowedPaths(usize),
+    /// The check is still pending; GitHub waits for it before merging the PR via auto-merge or
+    /// a merge queue.
+    PendingCheck(String),
+    /// The PR matched an allow rule (named so).
+    Rule(String),
 }
 
 #[derive(Debug)]
@@ -580,6 +1361,70 @@ pub enum Disqualification {
         conclusion: Option<String>,
     },
     State(Option<String>),
+    /// The update type is either not allowed, or couldn't be determined (in which case
+    /// `update_type` is `None`); `package` is `None` if the PR isn't recognised as an upgrade.
+    UpdateType {
+        package: Option<String>,
+        update_type: Option<UpdateType>,
+    },
+    /// The package isn't in the allow list; `None` if the package couldn't be determined.
+    PackageNotAllowed(Option<String>),
+    PackageDenied(String),
+    VersionDenied {
+        package: String,
+        version: String,
+    },
+    /// The ecosystem isn't in the list of allowed ones; `None` if it couldn't be determined.
+    Ecosystem(Option<String>),
+    /// No check run matches a required check.
+    RequiredCheckMissing(String),
+    TooFewChecks {
+        found: usize,
+        required: usize,
+    },
+    RequiredLabelMissing(String),
+    BlockingLabel(String),
+    /// Merges for the repo are frozen, as a PR (`pr`) carries the freeze label.
+    Frozen {
+        label: String,
+        pr: u64,
+    },
+    ApprovalMissing,
+    /// The PR (or the release it upgrades to) is newer than the minimum age.
+    CoolingDown {
+        eligible_at: DateTime<Utc>,
+        remaining: HumanDuration,
+    },
+    /// The PR is a draft, and drafts are skipped.
+    Draft,
+    /// The PR is older than the maximum age; it's left for a human to look at.
+    Stale {
+        max_age: HumanDuration,
+    },
+    /// The PR has merge conflicts, and the bot that created it has been asked to rebase it.
+    RebaseRequested(UpdateBot),
+    /// The PR has merge conflicts, and a rebase asked for earlier (at `requested_at`, if known) is
+    /// yet to happen.
+    RebasePending {
+        bot: UpdateBot,
+        requested_at: Option<DateTime<Utc>>,
+    },
+    /// The PR's head changed after it was evaluated, so it couldn't be merged safely.
+    HeadChanged,
+    /// The PR changes files that don't match any of the allowed paths.
+    PathsNotAllowed(Vec<String>),
+    TooManyChangedFiles {
+        found: u64,
+        max: u64,
+    },
+    TooManyAdditions {
+        found: u64,
+        max: u64,
+    },
+    /// The PR matched a deny rule (named so).
+    Rule(String),
+    /// Allow rules are configured, and the PR matched none of them.
+    NoRuleMatched,
 }
 
 impl Disqualification {
@@ -598,6 +1443,63 @@ impl Disqualification {
                 Some(s) => format!("state: {s}"),
                 None => "state: unknown".to_string(),
             },
+            Disqualification::UpdateType {
+                package,
+                update_type,
+            } => match (package, update_type) {
+                (Some(p), Some(u)) => format!("update type not allowed: {u} ({p})"),
+                (Some(p), None) => format!("update type unknown ({p})"),
+                (None, _) => "not a recognised upgrade".to_string(),
+            },
+            Disqualification::PackageNotAllowed(package) => match package {
+                Some(p) => format!("package not allowed: {p}"),
+                None => "package unknown".to_string(),
+            },
+            Disqualification::PackageDenied(package) => format!("package denied: {package}"),
+            Disqualification::VersionDenied { package, version } => {
+                format!("version denied: {package}@{version}")
+            }
+            Disqualification::Ecosystem(ecosystem) => match ecosystem {
+                Some(e) => format!("ecosystem not allowed: {e}"),
+                None => "ecosystem unknown".to_string(),
+            },
+            Disqualification::RequiredCheckMissing(check) => {
+                format!("required check missing: {check}")
+            }
+            Disqualification::TooFewChecks { found, required } => {
+                format!("too few checks: {found} (at least {required} required)")
+            }
+            Disqualification::RequiredLabelMissing(label) => {
+                format!("required label missing: {label}")
+            }
+            Disqualification::BlockingLabel(label) => format!("blocking label: {label}"),
+            Disqualification::Frozen { label, pr } => {
+                format!("merges frozen: {label} (#{pr})")
+            }
+            Disqualification::ApprovalMissing => "approval missing".to_string(),
+            Disqualification::CoolingDown { remaining, .. } => {
+                format!("cooling down: eligible in {remaining}")
+            }
+            Disqualification::Draft => "draft".to_string(),
+            Disqualification::Stale { max_age } => format!("stale: older than {max_age}"),
+            Disqualification::RebaseRequested(bot) => {
+                format!("conflicts: rebase requested ({bot})")
+            }
+            Disqualification::RebasePending { bot, .. } => {
+                format!("conflicts: rebase pending ({bot})")
+            }
+            Disqualification::HeadChanged => "head changed during evaluation".to_string(),
+            Disqualification::PathsNotAllowed(paths) => {
+                format!("paths not allowed: {}", paths.join(", "))
+            }
+            Disqualification::TooManyChangedFiles { found, max } => {
+                format!("too many changed files: {found} (at most {max} allowed)")
+            }
+            Disqualification::TooManyAdditions { found, max } => {
+                format!("too many additions: {found} (at most {max} allowed)")
+            }
+            Disqualification::Rule(name) => format!("denied by rule: {name}"),
+            Disqualification::NoRuleMatched => "no allow rule matched".to_string(),
         }
     }
 }
@@ -620,6 +1522,141 @@ mod tests {
     const PR_TITLE: &str = "build: bump clap from 4.5.39 to 4.5.40";
     const PR_URL: &str = "https://github.com/dhth/mrj/pull/1";
     const PR_HEAD: &str = "dependabot/cargo/clap-4.5.40";
+    const PR_SHA: &str = "9f2c1e4b7a3d8c6e5f0a1b2c3d4e5f6a7b8c9d0e";
+
+    #[test]
+    fn parsing_human_durations_works() {
+        // GIVEN
+        let values = ["30s", "15m", "1h30m", "2d", "1w", " 90s "];
+
+        // WHEN
+        let durations = values.map(|v| HumanDuration::parse(v).map(|d| d.duration.as_secs()));
+
+        // THEN
+        assert_eq!(
+            durations,
+            [
+                Some(30),
+                Some(900),
+                Some(5400),
+                Some(172_800),
+                Some(604_800),
+                Some(90)
+            ]
+        );
+    }
+
+    #[test]
+    fn writing_human_durations_works() {
+        // GIVEN
+        let durations = [59, 60, 3601, 41 * 60 * 60, 2 * 24 * 60 * 60 + 90];
+
+        // WHEN
+        let written =
+            durations.map(|secs| HumanDuration::from(Duration::from_secs(secs)).to_string());
+
+        // THEN
+        assert_eq!(written, ["1m", "1m", "1h1m", "1d17h", "2d2m"]);
+    }
+
+    #[test]
+    fn parsing_invalid_human_durations_fails() {
+        // GIVEN
+        let values = ["", "15", "m", "0s", "15 m", "1.5h", "10y", "-5m"];
+
+        // WHEN
+        // THEN
+        for value in values {
+            assert!(HumanDuration::parse(value).is_none(), "value: {value:?}");
+        }
+    }
+
+    #[test]
+    fn rendering_commit_templates_works() {
+        // GIVEN
+        let template = CommitTemplate::parse(
+            "{% if upgrade %}chore(deps): bump {{ upgrade.package }} from {{ upgrade.from }} to {{ upgrade.to }}{% else %}{{ title }}{% endif %} (#{{ number }})",
+        )
+        .expect("template should've been parsed");
+        let upgrade =
+            Upgrade::parse(PR_TITLE, None, PR_HEAD).expect("upgrade should've been parsed");
+        let contexts = [
+            commit_context(Some(CommitUpgrade::from(&upgrade))),
+            commit_context(None),
+        ];
+
+        // WHEN
+        let rendered = contexts
+            .iter()
+            .map(|c| {
+                template
+                    .render(c)
+                    .expect("template should've been rendered")
+            })
+            .collect::<Vec<_>>();
+
+        // THEN
+        assert_eq!(
+            rendered,
+            [
+                "chore(deps): bump clap from 4.5.39 to 4.5.40 (#1)",
+                "build: bump clap from 4.5.39 to 4.5.40 (#1)",
+            ]
+        );
+    }
+
+    #[test]
+    fn rendering_commit_template_with_trailers_works() {
+        // GIVEN
+        let template = CommitTemplate::parse(
+            r#"
+Upgrades {{ upgrade.package }} to {{ upgrade.to }} ({{ upgrade.update_type }}).
+
+Merged-by: mrj
+PR-author: {{ author }}
+"#,
+        )
+        .expect("template should've been parsed");
+        let upgrade =
+            Upgrade::parse(PR_TITLE, None, PR_HEAD).expect("upgrade should've been parsed");
+        let context = commit_context(Some(CommitUpgrade::from(&upgrade)));
+
+        // WHEN
+        let rendered = template
+            .render(&context)
+            .expect("template should've been rendered");
+
+        // THEN
+        assert_eq!(
+            rendered,
+            "Upgrades clap to 4.5.40 (patch).\n\nMerged-by: mrj\nPR-author: dependabot[bot]"
+        );
+    }
+
+    #[test]
+    fn rendering_commit_template_with_missing_upgrade_fails() {
+        // GIVEN
+        let template = CommitTemplate::parse("bump {{ upgrade.package }}")
+            .expect("template should've been parsed");
+
+        // WHEN
+        let result = template.render(&commit_context(None));
+
+        // THEN
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parsing_invalid_commit_templates_fails() {
+        // GIVEN
+        let values = ["{{ title", "{% if upgrade %}bump", "{{ title | }}"];
+
+        // WHEN
+        // THEN
+        for value in values {
+            assert!(CommitTemplate::parse(value).is_none(), "value: {value:?}");
+        }
+    }
 
     #[test]
     fn run_summary_works_as_expected() {
@@ -635,8 +1672,73 @@ mod tests {
                 merge_result_disqualified_failed_check(),
                 merge_result_disqualified_unknown_state(),
                 merge_result_disqualified_dirty_state(),
+                merge_result_disqualified_update_type(Some("clap"), Some(UpdateType::Major)),
+                merge_result_disqualified_update_type(Some("clap"), None),
+                merge_result_disqualified_update_type(None, None),
+                merge_result_disqualified(Disqualification::PackageNotAllowed(Some(
+                    "clap".to_string(),
+                ))),
+                merge_result_disqualified(Disqualification::PackageNotAllowed(None)),
+                merge_result_disqualified(Disqualification::PackageDenied("openssl".to_string())),
+                merge_result_disqualified(Disqualification::VersionDenied {
+                    package: "serde".to_string(),
+                    version: "1.0.999".to_string(),
+                }),
+                merge_result_disqualified(Disqualification::Ecosystem(Some(
+                    "npm_and_yarn".to_string(),
+                ))),
+                merge_result_disqualified(Disqualification::Ecosystem(None)),
+                merge_result_disqualified(Disqualification::RequiredCheckMissing(
+                    "test".to_string(),
+                )),
+                merge_result_disqualified(Disqualification::TooFewChecks {
+                    found: 1,
+                    required: 2,
+                }),
+                merge_result_disqualified(Disqualification::RequiredLabelMissing(
+                    "dependencies".to_string(),
+                )),
+                merge_result_disqualified(Disqualification::BlockingLabel(
+                    "do-not-merge".to_string(),
+                )),
+                merge_result_disqualified(Disqualification::Frozen {
+                    label: "merge-freeze".to_string(),
+                    pr: 12,
+                }),
+                merge_result_disqualified(Disqualification::ApprovalMissing),
+                merge_result_disqualified(Disqualification::CoolingDown {
+                    eligible_at: Utc::now(),
+                    remaining: HumanDuration::from(Duration::from_secs(41 * 60 * 60 + 30)),
+                }),
+                merge_result_disqualified(Disqualification::Draft),
+                merge_result_disqualified(Disqualification::Stale {
+                    max_age: HumanDuration::from(Duration::from_secs(30 * 24 * 60 * 60)),
+                }),
+                merge_result_disqualified(Disqualification::RebaseRequested(UpdateBot::Dependabot)),
+                merge_result_disqualified(Disqualification::RebasePending {
+                    bot: UpdateBot::Renovate,
+                    requested_at: None,
+                }),
+                merge_result_disqualified(Disqualification::HeadChanged),
+                merge_result_disqualified(Disqualification::PathsNotAllowed(vec![
+                    "src/main.rs".to_string(),
+                    "build.rs".to_string(),
+                ])),
+                merge_result_disqualified(Disqualification::TooManyChangedFiles {
+                    found: 12,
+                    max: 10,
+                }),
+                merge_result_disqualified(Disqualification::TooManyAdditions {
+                    found: 1200,
+                    max: 1000,
+                }),
+                merge_result_disqualified(Disqualification::Rule("no security fixes".to_string())),
+                merge_result_disqualified(Disqualification::NoRuleMatched),
                 merge_result_errored(),
                 merge_result_qualified(),
+                merge_result_branch_updated(),
+                merge_result_enqueued(false),
+                merge_result_enqueued(true),
             ]),
         });
 
@@ -660,10 +1762,68 @@ mod tests {
             reason: "state: unknown"
           - pr_url: "https://github.com/dhth/mrj/pull/1"
             reason: "state: dirty"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "update type not allowed: major (clap)"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: update type unknown (clap)
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: not a recognised upgrade
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "package not allowed: clap"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: package unknown
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "package denied: openssl"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "version denied: serde@1.0.999"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "ecosystem not allowed: npm_and_yarn"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: ecosystem unknown
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "required check missing: test"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "too few checks: 1 (at least 2 required)"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "required label missing: dependencies"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "blocking label: do-not-merge"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "merges frozen: merge-freeze (#12)"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: approval missing
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "cooling down: eligible in 1d17h1m"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: draft
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "stale: older than 30d"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "conflicts: rebase requested (dependabot)"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "conflicts: rebase pending (renovate)"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: head changed during evaluation
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "paths not allowed: src/main.rs, build.rs"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "too many changed files: 12 (at most 10 allowed)"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "too many additions: 1200 (at most 1000 allowed)"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: "denied by rule: no security fixes"
+          - pr_url: "https://github.com/dhth/mrj/pull/1"
+            reason: no allow rule matched
         num_errors: 1
         prs_merged:
           - repo: dhth/mrj
             title: "build: bump clap from 4.5.39 to 4.5.40"
+        branches_updated:
+          - repo: dhth/mrj
+            title: "build: bump serde from 1.0.218 to 1.0.219"
+        prs_enqueued:
+          - repo: dhth/mrj
+            title: "build: bump tokio from 1.45.0 to 1.45.1"
         "#);
     }
 
@@ -674,6 +1834,8 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![],
             state: PRDisqualified(Disqualification::Head("improve-tests".to_string())),
         })
@@ -686,6 +1848,8 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
             state: PRDisqualified(Disqualification::Author(None)),
         })
@@ -698,6 +1862,8 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
             state: PRDisqualified(Disqualification::Author(Some(
                 "untrusted-author".to_string(),
@@ -712,6 +1878,8 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
             state: PRDisqualified(Disqualification::Check {
                 name: "lint".to_string(),
@@ -727,6 +1895,8 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
             state: PRDisqualified(Disqualification::Check {
                 name: "lint".to_string(),
@@ -742,6 +1912,8 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
             state: PRDisqualified(Disqualification::State(None)),
         })
@@ -754,11 +1926,47 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
             state: PRDisqualified(Disqualification::State(Some("dirty".to_string()))),
         })
     }
 
+    fn merge_result_disqualified_update_type(
+        package: Option<&str>,
+        update_type: Option<UpdateType>,
+    ) -> MergeResult {
+        MergeResult::Disqualified(PRCheck {
+            number: 1,
+            title: PR_TITLE.to_string(),
+            url: PR_URL.to_string(),
+            pr_created_at: None,
+            pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
+            qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
+            state: PRDisqualified(Disqualification::UpdateType {
+                package: package.map(|p| p.to_string()),
+                update_type,
+            }),
+        })
+    }
+
+    fn merge_result_disqualified(dq: Disqualification) -> MergeResult {
+        MergeResult::Disqualified(PRCheck {
+            number: 1,
+            title: PR_TITLE.to_string(),
+            url: PR_URL.to_string(),
+            pr_created_at: None,
+            pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
+            qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
+            state: PRDisqualified(dq),
+        })
+    }
+
     fn merge_result_errored() -> MergeResult {
         MergeResult::Errored(PRCheck {
             number: 1,
@@ -766,6 +1974,8 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
             state: PRCheckErrored(anyhow::anyhow!("couldn't merge PR: GitHub API was down")),
         })
@@ -778,8 +1988,76 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
-            state: PRCheckFinished,
+            state: PRCheckFinished {
+                head_sha: PR_SHA.to_string(),
+                merge_commit_sha: None,
+                post_merge: vec![],
+            },
+        })
+    }
+
+    fn commit_context(upgrade: Option<CommitUpgrade>) -> CommitContext {
+        CommitContext {
+            number: 1,
+            title: PR_TITLE.to_string(),
+            body: "Bumps clap from 4.5.39 to 4.5.40.".to_string(),
+            author: "dependabot[bot]".to_string(),
+            head_ref: PR_HEAD.to_string(),
+            ecosystem: Some("cargo".to_string()),
+            upgrade,
+        }
+    }
+
+    fn merge_result_branch_updated() -> MergeResult {
+        MergeResult::BranchUpdated(PRCheck {
+            number: 2,
+            title: "build: bump serde from 1.0.218 to 1.0.219".to_string(),
+            url: "https://github.com/dhth/mrj/pull/2".to_string(),
+            pr_created_at: None,
+            pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
+            qualifications: vec![],
+            state: PRBranchUpdated,
+        })
+    }
+
+    #[test]
+    fn only_prs_handed_over_during_the_run_take_merge_slots() {
+        // GIVEN
+        let results = [
+            merge_result_qualified(),
+            merge_result_enqueued(false),
+            merge_result_enqueued(true),
+            merge_result_branch_updated(),
+            merge_result_errored(),
+        ];
+
+        // WHEN
+        let takes_slot = results.map(|r| r.takes_merge_slot());
+
+        // THEN
+        assert_eq!(takes_slot, [true, true, false, false, false]);
+    }
+
+    fn merge_result_enqueued(already_handed_over: bool) -> MergeResult {
+        MergeResult::Enqueued(PRCheck {
+            number: 3,
+            title: "build: bump tokio from 1.45.0 to 1.45.1".to_string(),
+            url: "https://github.com/dhth/mrj/pull/3".to_string(),
+            pr_created_at: None,
+            pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
+            qualifications: vec![],
+            state: PREnqueued {
+                strategy: MergeStrategy::AutoMerge,
+                head_sha: PR_SHA.to_string(),
+                already_handed_over,
+            },
         })
     }
 }
@@ -5,11 +5,13 @@ mod domain;
 mod merge;
 mod persistence;
 mod report;
+mod rule;
+mod upgrade;
 
 use anyhow::Context;
 use args::Args;
 use args::{ConfigCommand, MrjCommand, ReportCommand};
-use auth::get_token;
+use auth::{get_approval_token, get_token};
 use clap::Parser;
 use config::get_config;
 use merge::{RunBehaviours, merge_prs};
@@ -42,12 +44,14 @@ async fn main() -> anyhow::Result<()> {
             show_repos_with_no_prs,
             show_prs_from_untrusted_authors,
             show_prs_with_unmatched_head,
+            show_draft_prs,
+            show_stale_prs,
             execute,
             plain_stdout,
         } => {
             let config = Arc::new(get_config(config_file)?);
 
-            if config.repos.is_empty() && repos.is_empty() {
+            if config.repos.is_empty() && config.search.is_none() && repos.is_empty() {
                 anyhow::bail!("no repos to run for");
             }
 
@@ -61,6 +65,16 @@ async fn main() -> anyhow::Result<()> {
             );
             let client = octocrab::instance();
 
+            let approver = match get_approval_token()? {
+                Some(approval_token) => Arc::new(
+                    octocrab::Octocrab::builder()
+                        .user_access_token(approval_token)
+                        .build()
+                        .context("couldn't build github client for approvals")?,
+                ),
+                None => Arc::clone(&client),
+            };
+
             let output_path_to_use = if output_to_file {
                 Some(output_path)
             } else {
@@ -76,12 +90,20 @@ async fn main() -> anyhow::Result<()> {
                 show_repos_with_no_prs,
                 show_prs_from_untrusted_authors,
                 show_prs_with_unmatched_head,
+                show_draft_prs,
+                show_stale_prs,
                 execute,
                 plain_stdout,
             };
 
-            let Some(results) =
-                merge_prs(client, Arc::clone(&config), repos, run_behaviours.clone()).await?
+            let Some(results) = merge_prs(
+                client,
+                approver,
+                Arc::clone(&config),
+                repos,
+                run_behaviours.clone(),
+            )
+            .await?
             else {
                 return Ok(());
             };
@@ -8,6 +8,8 @@ pub struct RunBehaviours {
     pub show_repos_with_no_prs: bool,
     pub show_prs_from_untrusted_authors: bool,
     pub show_prs_with_unmatched_head: bool,
+    pub show_draft_prs: bool,
+    pub show_stale_prs: bool,
     pub execute: bool,
     pub plain_stdout: bool,
 }
@@ -22,6 +24,8 @@ impl RunBehaviours {
             show_repos_with_no_prs: false,
             show_prs_from_untrusted_authors: false,
             show_prs_with_unmatched_head: false,
+            show_draft_prs: false,
+            show_stale_prs: false,
             execute: false,
             plain_stdout: true,
         }
@@ -32,6 +36,16 @@ impl RunBehaviours {
         self
     }
 
+    pub(super) fn show_draft_prs(mut self) -> Self {
+        self.show_draft_prs = true;
+        self
+    }
+
+    pub(super) fn show_stale_prs(mut self) -> Self {
+        self.show_stale_prs = true;
+        self
+    }
+
     pub(super) fn show_prs_from_untrusted_authors(mut self) -> Self {
         self.show_prs_from_untrusted_authors = true;
         self
@@ -0,0 +1,300 @@
+use crate::config::Config;
+use crate::domain::{Repo, RepoSelector};
+use anyhow::Context;
+use octocrab::Octocrab;
+use octocrab::models::Repository;
+use std::collections::{HashMap, HashSet};
+
+/// The bits of a repository listing that matter when expanding repo selectors.
+#[derive(Debug, Clone)]
+pub(super) struct ListedRepo {
+    pub(super) name: String,
+    pub(super) archived: bool,
+    pub(super) fork: bool,
+    pub(super) topics: Vec<String>,
+}
+
+impl From<Repository> for ListedRepo {
+    fn from(repo: Repository) -> Self {
+        Self {
+            name: repo.name,
+            archived: repo.archived.unwrap_or_default(),
+            fork: repo.fork.unwrap_or_default(),
+            topics: repo.topics.unwrap_or_default(),
+        }
+    }
+}
+
+/// Expands the repo selectors in the config into a list of repos, listing repos of owners via
+/// GitHub's API only when a selector needs it.
+pub(super) async fn discover_repos(
+    client: &Octocrab,
+    config: &Config,
+) -> anyhow::Result<Vec<Repo>> {
+    let mut listings = HashMap::new();
+
+    for selector in &config.repos {
+        let owner = selector.owner();
+        if matches!(selector, RepoSelector::Repo(_)) || listings.contains_key(owner) {
+            continue;
+        }
+
+        let listed = list_owner_repos(client, owner, config.max_pages)
+            .await
+            .with_context(|| format!("couldn't list repos for \"{owner}\""))?;
+        listings.insert(owner.to_string(), listed);
+    }
+
+    Ok(select_repos(config, &listings))
+}
+
+pub(super) fn select_repos(
+    config: &Config,
+    listings: &HashMap<String, Vec<ListedRepo>>,
+) -> Vec<Repo> {
+    let mut seen = HashSet::new();
+    let mut selected = vec![];
+
+    for selector in &config.repos {
+        let candidates = match selector {
+            RepoSelector::Repo(repo) => vec![repo.clone()],
+            RepoSelector::Pattern { owner, pattern } => listings
+                .get(owner)
+                .into_iter()
+                .flatten()
+                .filter(|r| is_selectable(r, config) && pattern.is_match(&r.name))
+                .map(|r| Repo {
+                    owner: owner.clone(),
+                    repo: r.name.clone(),
+                })
+                .collect(),
+            RepoSelector::Topic { owner, topic } => listings
+                .get(owner)
+                .into_iter()
+                .flatten()
+                .filter(|r| is_selectable(r, config) && r.topics.contains(topic))
+                .map(|r| Repo {
+                    owner: owner.clone(),
+                    repo: r.name.clone(),
+                })
+                .collect(),
+        };
+
+        for repo in candidates {
+            let full_name = repo.to_string();
+            if config.exclude.iter().any(|g| g.is_match(&full_name)) {
+                continue;
+            }
+
+            if seen.insert(full_name) {
+                selected.push(repo);
+            }
+        }
+    }
+
+    selected
+}
+
+fn is_selectable(repo: &ListedRepo, config: &Config) -> bool {
+    !(config.skip_archived && repo.archived || config.skip_forks && repo.fork)
+}
+
+/// Lists the repos of an owner; fails if they don't fit in the maximum number of pages, as the
+/// repos left out would never get run for.
+async fn list_owner_repos(
+    client: &Octocrab,
+    owner: &str,
+    max_pages: u32,
+) -> anyhow::Result<Vec<ListedRepo>> {
+    let mut page = match client.orgs(owner).list_repos().per_page(100).send().await {
+        Ok(page) => page,
+        Err(octocrab::Error::GitHub { source, .. }) if source.status_code.as_u16() == 404 => {
+            // not an organization; fall back to listing the user's repos, which only includes
+            // private ones when the user is the one the token belongs to
+            if is_authenticated_user(client, owner).await {
+                client
+                    .current()
+                    .list_repos_for_authenticated_user()
+                    .affiliation("owner")
+                    .per_page(100)
+                    .send()
+                    .await
+                    .context("couldn't list repos of the authenticated user")?
+            } else {
+                client
+                    .users(owner)
+                    .repos()
+                    .per_page(100)
+                    .send()
+                    .await
+                    .context("couldn't list user repos")?
+            }
+        }
+        Err(err) => return Err(err).context("couldn't list organization repos"),
+    };
+
+    let mut repos = std::mem::take(&mut page.items);
+
+    for _ in 1..max_pages {
+        let Some(mut next_page) = client
+            .get_page::<Repository>(&page.next)
+            .await
+            .context("couldn't get next page of repos")?
+        else {
+            break;
+        };
+
+        repos.append(&mut next_page.items);
+        page = next_page;
+    }
+
+    if page.next.is_some() {
+        anyhow::bail!(
+            "couldn't fetch all repos within {max_pages} page(s); consider raising max_pages"
+        );
+    }
+
+    Ok(repos.into_iter().map(ListedRepo::from).collect())
+}
+
+/// Tokens that don't belong to a user (eg. those of GitHub Apps) can't look up the authenticated
+/// user; those can't own repos either.
+async fn is_authenticated_user(client: &Octocrab, owner: &str) -> bool {
+    client
+        .current()
+        .user()
+        .await
+        .is_ok_and(|user| user.login.eq_ignore_ascii_case(owner))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merge::tests::config;
+    use insta::assert_yaml_snapshot;
+
+    #[test]
+    fn selecting_repos_expands_patterns_and_topics() {
+        // GIVEN
+        let config = config(
+            r#"
+repos = [
+    "org/explicit",
+    "org/service-*",
+    { owner = "org", topic = "auto-merge" },
+]
+"#,
+        );
+
+        // WHEN
+        let repos = select_repos(&config, &listings());
+
+        // THEN
+        assert_yaml_snapshot!(repos, @"
+        - owner: org
+          repo: explicit
+        - owner: org
+          repo: service-a
+        - owner: org
+          repo: service-b
+        - owner: org
+          repo: tool
+        ");
+    }
+
+    #[test]
+    fn selecting_repos_skips_archived_repos_and_forks_by_default() {
+        // GIVEN
+        let config = config(
+            r#"
+repos = ["org/*"]
+"#,
+        );
+
+        // WHEN
+        let repos = select_repos(&config, &listings());
+
+        // THEN
+        assert_yaml_snapshot!(repos, @"
+        - owner: org
+          repo: service-a
+        - owner: org
+          repo: service-b
+        - owner: org
+          repo: tool
+        ");
+    }
+
+    #[test]
+    fn selecting_repos_includes_archived_repos_and_forks_when_requested() {
+        // GIVEN
+        let config = config(
+            r#"
+repos = ["org/*"]
+skip_archived = false
+skip_forks = false
+"#,
+        );
+
+        // WHEN
+        let repos = select_repos(&config, &listings());
+
+        // THEN
+        assert_yaml_snapshot!(repos, @"
+        - owner: org
+          repo: service-a
+        - owner: org
+          repo: service-b
+        - owner: org
+          repo: service-old
+        - owner: org
+          repo: tool
+        - owner: org
+          repo: upstream-fork
+        ");
+    }
+
+    #[test]
+    fn selecting_repos_applies_exclusions_to_all_repos() {
+        // GIVEN
+        let config = config(
+            r#"
+repos = ["org/explicit", "org/*"]
+exclude = ["org/explicit", "*/service-b"]
+"#,
+        );
+
+        // WHEN
+        let repos = select_repos(&config, &listings());
+
+        // THEN
+        assert_yaml_snapshot!(repos, @"
+        - owner: org
+          repo: service-a
+        - owner: org
+          repo: tool
+        ");
+    }
+
+    fn listings() -> HashMap<String, Vec<ListedRepo>> {
+        HashMap::from([(
+            "org".to_string(),
+            vec![
+                listed_repo("service-a", false, false, &[]),
+                listed_repo("service-b", false, false, &["auto-merge"]),
+                listed_repo("service-old", true, false, &["auto-merge"]),
+                listed_repo("tool", false, false, &["auto-merge"]),
+                listed_repo("upstream-fork", false, true, &["auto-merge"]),
+            ],
+        )])
+    }
+
+    fn listed_repo(name: &str, archived: bool, fork: bool, topics: &[&str]) -> ListedRepo {
+        ListedRepo {
+            name: name.to_string(),
+            archived,
+            fork,
+            topics: topics.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+}
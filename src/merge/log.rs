@@ -1,7 +1,8 @@
 use super::behaviours::RunBehaviours;
 use crate::config::Config;
 use crate::domain::{
-    Disqualification, GhApiQueryParam, MergeResult, Qualification, RepoResult, RunSummary,
+    Disqualification, GhApiQueryParam, MergeResult, MergeStrategy, PREnqueued, PostMergeAction,
+    PostMergeOutcome, Qualification, Repo, RepoResult, RunSummary,
 };
 use anyhow::Context;
 use chrono::{DateTime, Utc};
@@ -11,9 +12,22 @@ use std::io::Write;
 
 const BANNER: &str = include_str!("assets/banner.txt");
 const AUTHOR: &str = "[ author ]  ";
+const BASE: &str = "[ base   ]  ";
 const HEAD: &str = "[ head  ]  ";
 const CHECK: &str = "[ check  ]  ";
 const STATE: &str = "[ state  ]  ";
+const UPDATE: &str = "[ update ]  ";
+const PACKAGE: &str = "[ pkg    ]  ";
+const ECOSYSTEM: &str = "[ eco    ]  ";
+const LABEL: &str = "[ label  ]  ";
+const REVIEW: &str = "[ review ]  ";
+const AGE: &str = "[ age    ]  ";
+const DRAFT: &str = "[ draft  ]  ";
+const SLOT: &str = "[ slot   ]  ";
+const REBASE: &str = "[ rebase ]  ";
+const FILES: &str = "[ files  ]  ";
+const AFTER: &str = "[ after  ]  ";
+const RULE: &str = "[ rule   ]  ";
 
 pub(super) struct RunLogger<W: Write> {
     w: W,
@@ -73,6 +87,44 @@ PRs merged
             ))
         };
 
+        let branches_updated = if summary.branches_updated.is_empty() {
+            None
+        } else {
+            Some(format!(
+                r#"
+
+Branches updated
+---
+
+{}"#,
+                summary
+                    .branches_updated
+                    .iter()
+                    .map(|pr| format!("- [{}] {}", pr.repo, pr.title))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            ))
+        };
+
+        let prs_enqueued = if summary.prs_enqueued.is_empty() {
+            None
+        } else {
+            Some(format!(
+                r#"
+
+PRs handed over to GitHub (auto-merge or merge queue)
+---
+
+{}"#,
+                summary
+                    .prs_enqueued
+                    .iter()
+                    .map(|pr| format!("- [{}] {}", pr.repo, pr.title))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            ))
+        };
+
         let disqualifications_summary = if !self.behaviours.skip_disqualifications_in_summary
             && !summary.disqualifications.is_empty()
         {
@@ -108,11 +160,13 @@ Disqualifications
 
 - PRs merged:                    {}
 - PRs disqualified:              {}
-- Errors encountered:            {}{}{}"#,
+- Errors encountered:            {}{}{}{}{}"#,
             summary.prs_merged.len(),
             summary.disqualifications.len(),
             summary.num_errors,
             prs_merged.unwrap_or_default(),
+            prs_enqueued.unwrap_or_default(),
+            branches_updated.unwrap_or_default(),
             disqualifications_summary.unwrap_or_default(),
         );
 
@@ -170,19 +224,72 @@ Disqualifications
             .collect()
     }
 
-    pub(super) fn print_startup_info(&mut self, config: &Config, now: DateTime<Utc>) {
+    pub(super) fn print_startup_info(
+        &mut self,
+        config: &Config,
+        repos: &[Repo],
+        now: DateTime<Utc>,
+    ) {
         self.info(&format!("The time right now is {now}"));
 
+        if let Some(query) = &config.search {
+            self.info(&format!(
+                "I'm looking for PRs via the search query \"{query}\""
+            ));
+        }
+
+        self.info(&format!(
+            "I'm running for {} repo(s): {}",
+            repos.len(),
+            repos
+                .iter()
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
         if let Some(b) = &config.base_branch {
             self.info(&format!(
                 "I'm only looking for PRs where the base branch is \"{b}\""
             ));
         }
 
+        if !config.base_branches.is_empty() {
+            self.info(&format!(
+                "I'm only looking for PRs where the base branch matches one of: {}",
+                config
+                    .base_branches
+                    .iter()
+                    .map(|b| format!("\"{b}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if !config.base_overrides.is_empty() {
+            self.info(&format!(
+                "I'm applying overrides for PRs against these base branches: {}",
+                config
+                    .base_overrides
+                    .iter()
+                    .map(|(b, _)| format!("\"{b}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
         if config.merge_if_blocked {
             self.info("I will merge PRs even if they're blocked");
         }
 
+        if config.update_behind_branches {
+            self.info("I will update the branches of PRs that are behind their base");
+        }
+
+        if config.request_rebases {
+            self.info("I will ask bots to rebase PRs that have merge conflicts");
+        }
+
         if !config.merge_if_checks_skipped {
             self.info("I won't merge PRs if checks are skipped");
         }
@@ -191,6 +298,153 @@ Disqualifications
             self.info("I will merge PRs if checks conclude with a neutral status");
         }
 
+        if !config.required_checks.is_empty() {
+            self.info(&format!(
+                "I'm only merging PRs where these checks are present: {}",
+                config
+                    .required_checks
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if config.min_checks > 0 {
+            self.info(&format!(
+                "I'm only merging PRs with at least {} check(s)",
+                config.min_checks
+            ));
+        }
+
+        if let Some(w) = &config.wait_for_pending_checks {
+            self.info(&format!(
+                "I will wait up to {} for pending checks to conclude, polling every {}",
+                w.timeout, w.poll_interval
+            ));
+        }
+
+        if !config.blocking_labels.is_empty() {
+            self.info(&format!(
+                "I won't merge PRs with any of these labels: {}",
+                config.blocking_labels.join(", ")
+            ));
+        }
+
+        if let Some(label) = &config.freeze_label {
+            self.info(&format!(
+                "I won't merge any PRs in a repo where a PR has the label \"{label}\""
+            ));
+        }
+
+        if config.require_approval {
+            self.info("I'm only merging PRs that have been approved by a trusted reviewer");
+        }
+
+        if config.auto_approve {
+            self.info("I will approve PRs that pass every other rule before merging them");
+        }
+
+        if !config.skip_drafts {
+            self.info("I will consider draft PRs as well");
+        }
+
+        if let Some(max_pr_age) = &config.max_pr_age {
+            self.info(&format!(
+                "I'm leaving PRs older than {max_pr_age} for a human to look at"
+            ));
+        }
+
+        if let Some(min_pr_age) = &config.min_pr_age {
+            self.info(&format!(
+                "I'm only merging PRs (and releases) that are at least {min_pr_age} old"
+            ));
+        }
+
+        if let Some(update_types) = &config.allowed_update_types {
+            self.info(&format!(
+                "I'm only merging these types of updates: {}",
+                update_types
+                    .iter()
+                    .map(|u| u.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if config.max_merges_per_repo > 1 {
+            self.info(&format!(
+                "I will merge up to {} PRs per repo",
+                config.max_merges_per_repo
+            ));
+        }
+
+        match config.merge_strategy {
+            MergeStrategy::Direct => {}
+            MergeStrategy::AutoMerge => {
+                self.info("I will enable auto-merge on PRs, instead of merging them myself");
+            }
+            MergeStrategy::MergeQueue => {
+                self.info("I will add PRs to the merge queue, instead of merging them myself");
+            }
+        }
+
+        if config.commit_title_template.is_some() || config.commit_message_template.is_some() {
+            self.info("I will use the configured templates for merge commits");
+        }
+
+        if config.delete_branch_after_merge {
+            self.info("I will delete the branches of PRs after merging them");
+        }
+
+        if config.close_superseded {
+            self.info("I will close PRs that are superseded by the ones I merge");
+        }
+
+        if let Some(merge_delay) = &config.merge_delay {
+            self.info(&format!(
+                "I will wait {merge_delay} after each merge before moving on to the next PR in a repo"
+            ));
+        }
+
+        if !config.ecosystems.is_empty() {
+            self.info(&format!(
+                "I'm only looking at PRs for these ecosystems: {}",
+                config.ecosystems.join(", ")
+            ));
+        }
+
+        if !config.allowed_paths.is_empty() {
+            self.info(&format!(
+                "I'm only merging PRs that change files matching these paths: {}",
+                config
+                    .allowed_paths
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if let Some(max_changed_files) = config.max_changed_files {
+            self.info(&format!(
+                "I'm only merging PRs that change at most {max_changed_files} file(s)"
+            ));
+        }
+
+        if let Some(max_additions) = config.max_additions {
+            self.info(&format!(
+                "I'm only merging PRs that add at most {max_additions} line(s)"
+            ));
+        }
+
+        for rule in &config.rules {
+            self.info(&format!(
+                "I'm evaluating the rule \"{}\": {}",
+                rule.name, rule.rule
+            ));
+        }
+
         if self.behaviours.show_repos_with_no_prs {
             self.info("I will show repositories that have no PRs");
         }
@@ -203,11 +457,55 @@ Disqualifications
             self.info("I will show PRs from where head doesn't match configured head pattern");
         }
 
+        if self.behaviours.show_draft_prs {
+            self.info("I will show draft PRs");
+        }
+
+        if self.behaviours.show_stale_prs && config.max_pr_age.is_some() {
+            self.info("I will show PRs older than the configured maximum age");
+        }
+
         self.info(&format!(
             r#"I'm sorting PRs based on "{}" in the "{}" direction"#,
             config.sort_by.readable_repr(),
             config.sort_direction.readable_repr()
         ));
+
+        if !config.owner_overrides.is_empty() {
+            self.info(&format!(
+                "I'm using custom settings for repos owned by: {}",
+                config
+                    .owner_overrides
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if !config.repo_overrides.is_empty() {
+            self.info(&format!(
+                "I'm using custom settings for these repos: {}",
+                config
+                    .repo_overrides
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if !config.ecosystem_overrides.is_empty() {
+            self.info(&format!(
+                "I'm using custom settings for PRs of these ecosystems: {}",
+                config
+                    .ecosystem_overrides
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
     }
 
     pub(super) fn print_conclusion(&mut self, now: DateTime<Utc>, num_seconds: i64) {
@@ -262,8 +560,17 @@ Disqualifications
             MergeResult::Errored(pr_check) => {
                 self.error(pr_check.state.reason());
             }
-            MergeResult::Qualified(_) => {
+            MergeResult::Qualified(pr_check) => {
                 self.merge();
+                for outcome in &pr_check.state.post_merge {
+                    self.post_merge(outcome);
+                }
+            }
+            MergeResult::BranchUpdated(_) => {
+                self.branch_updated();
+            }
+            MergeResult::Enqueued(pr_check) => {
+                self.enqueued(&pr_check.state);
             }
         }
     }
@@ -298,6 +605,9 @@ Disqualifications
 
     fn qualification(&mut self, q: &Qualification) {
         let msg = match q {
+            Qualification::Base(b) => {
+                format!("{BASE} \"{b}\" matches one of the allowed base branches")
+            }
             Qualification::Head(h) => {
                 format!("{HEAD} \"{h}\" matches the allowed head pattern")
             }
@@ -308,6 +618,35 @@ Disqualifications
                 format!("{CHECK} \"{name}\" concluded with desired status: \"{conclusion}\"",)
             }
             Qualification::State(s) => format!("{STATE} \"{s}\" is desirable"),
+            Qualification::UpdateType {
+                package,
+                update_type,
+            } => format!("{UPDATE} \"{package}\" is a {update_type} update, which is allowed"),
+            Qualification::Package(p) => {
+                format!("{PACKAGE} \"{p}\" passes the package allow/deny lists")
+            }
+            Qualification::Ecosystem(e) => {
+                format!("{ECOSYSTEM} \"{e}\" is in the list of allowed ecosystems")
+            }
+            Qualification::IgnoredCheck { name, conclusion } => match conclusion {
+                Some(c) => format!("{CHECK} \"{name}\" is ignored (concluded with \"{c}\")"),
+                None => format!("{CHECK} \"{name}\" is ignored (hasn't concluded yet)"),
+            },
+            Qualification::Label(l) => format!("{LABEL} \"{l}\" is one of the required labels"),
+            Qualification::Approval(r) => format!("{REVIEW} \"{r}\" has approved the PR"),
+            Qualification::AutoApproval => {
+                format!("{REVIEW} nobody has approved the PR yet; I'll approve it before merging")
+            }
+            Qualification::MergeSlot { slot, max } => {
+                format!("{SLOT} this is merge {slot} of up to {max} for this repo")
+            }
+            Qualification::AllowedPaths(num_files) => {
+                format!("{FILES} all {num_files} changed file(s) match the allowed paths")
+            }
+            Qualification::PendingCheck(name) => {
+                format!("{CHECK} \"{name}\" is pending; GitHub will wait for it before merging")
+            }
+            Qualification::Rule(name) => format!("{RULE} the PR is allowed by the rule \"{name}\""),
         };
 
         let output = if self.behaviours.plain_stdout {
@@ -342,6 +681,103 @@ Disqualifications
                     "{STATE} Github returned with an empty mergeable state; skipping as I can't make any assumptions here"
                 ),
             },
+            Disqualification::UpdateType {
+                package,
+                update_type,
+            } => match (package, update_type) {
+                (Some(p), Some(u)) => {
+                    format!("{UPDATE} \"{p}\" is a {u} update, which is not allowed")
+                }
+                (Some(p), None) => format!(
+                    "{UPDATE} couldn't determine the update type for \"{p}\"; skipping as I can't make any assumptions here"
+                ),
+                (None, _) => format!(
+                    "{UPDATE} couldn't recognise an upgrade in the PR title; skipping as I can't make any assumptions here"
+                ),
+            },
+            Disqualification::PackageNotAllowed(package) => match package {
+                Some(p) => format!("{PACKAGE} \"{p}\" is not in the list of allowed packages"),
+                None => format!(
+                    "{PACKAGE} couldn't determine the package being upgraded; skipping as I can't make any assumptions here"
+                ),
+            },
+            Disqualification::PackageDenied(p) => {
+                format!("{PACKAGE} \"{p}\" is in the list of denied packages")
+            }
+            Disqualification::VersionDenied { package, version } => {
+                format!("{PACKAGE} \"{package}@{version}\" is in the list of denied versions")
+            }
+            Disqualification::Ecosystem(ecosystem) => match ecosystem {
+                Some(e) => format!("{ECOSYSTEM} \"{e}\" is not in the list of allowed ecosystems"),
+                None => format!(
+                    "{ECOSYSTEM} couldn't determine the ecosystem; skipping as I can't make any assumptions here"
+                ),
+            },
+            Disqualification::RequiredCheckMissing(c) => {
+                format!("{CHECK} no check matching \"{c}\" was found, but it's required")
+            }
+            Disqualification::TooFewChecks { found, required } => {
+                format!("{CHECK} found {found} check(s), but at least {required} are required")
+            }
+            Disqualification::RequiredLabelMissing(l) => {
+                format!("{LABEL} \"{l}\" is required, but the PR doesn't have it")
+            }
+            Disqualification::BlockingLabel(l) => {
+                format!("{LABEL} \"{l}\" is in the list of blocking labels")
+            }
+            Disqualification::Frozen { label, pr } => {
+                format!("{LABEL} merges are frozen, as PR #{pr} has the label \"{label}\"")
+            }
+            Disqualification::ApprovalMissing => {
+                format!("{REVIEW} the PR hasn't been approved by a trusted reviewer")
+            }
+            Disqualification::CoolingDown {
+                eligible_at,
+                remaining,
+            } => format!(
+                "{AGE} the PR is too new; it'll be eligible in {remaining} (at {})",
+                eligible_at.format("%Y-%m-%d %H:%M UTC")
+            ),
+            Disqualification::Draft => {
+                format!("{DRAFT} the PR is a draft; skipping it until it's ready for review")
+            }
+            Disqualification::Stale { max_age } => {
+                format!("{AGE} the PR is older than {max_age}; leaving it for a human to look at")
+            }
+            Disqualification::HeadChanged => {
+                format!("{HEAD} the PR's head changed while I was evaluating it")
+            }
+            Disqualification::PathsNotAllowed(paths) => format!(
+                "{FILES} these changed files don't match the allowed paths: {}",
+                paths.join(", ")
+            ),
+            Disqualification::TooManyChangedFiles { found, max } => {
+                format!("{FILES} the PR changes {found} file(s), but at most {max} are allowed")
+            }
+            Disqualification::TooManyAdditions { found, max } => {
+                format!("{FILES} the PR adds {found} line(s), but at most {max} are allowed")
+            }
+            Disqualification::Rule(name) => {
+                format!("{RULE} the PR is denied by the rule \"{name}\"")
+            }
+            Disqualification::NoRuleMatched => {
+                format!("{RULE} the PR doesn't match any of the allow rules")
+            }
+            Disqualification::RebaseRequested(bot) if self.behaviours.execute => {
+                format!("{REBASE} the PR has merge conflicts; I've asked {bot} to rebase it")
+            }
+            Disqualification::RebaseRequested(bot) => format!(
+                "{REBASE} the PR has merge conflicts; I would've asked {bot} to rebase it if this weren't a dry run"
+            ),
+            Disqualification::RebasePending { bot, requested_at } => match requested_at {
+                Some(at) => format!(
+                    "{REBASE} the PR has merge conflicts; {bot} was asked to rebase it at {}, and hasn't done so yet",
+                    at.format("%Y-%m-%d %H:%M UTC")
+                ),
+                None => format!(
+                    "{REBASE} the PR has merge conflicts; {bot} was asked to rebase it, and hasn't done so yet"
+                ),
+            },
         };
 
         let output = if self.behaviours.plain_stdout {
@@ -379,6 +815,85 @@ Disqualifications
         let _ = writeln!(self.w, "        {output}");
     }
 
+    fn post_merge(&mut self, outcome: &PostMergeOutcome) {
+        let execute = self.behaviours.execute;
+        let msg = match (&outcome.action, &outcome.error) {
+            (PostMergeAction::DeleteBranch(b), None) if execute => {
+                format!("{AFTER} deleted the PR's branch \"{b}\"")
+            }
+            (PostMergeAction::DeleteBranch(b), None) => {
+                format!("{AFTER} would've deleted the PR's branch \"{b}\"")
+            }
+            (PostMergeAction::DeleteBranch(b), Some(err)) => {
+                format!("{AFTER} couldn't delete the PR's branch \"{b}\": {err}")
+            }
+            (PostMergeAction::CloseSuperseded(n), None) if execute => {
+                format!("{AFTER} closed PR #{n}, which this PR supersedes")
+            }
+            (PostMergeAction::CloseSuperseded(n), None) => {
+                format!("{AFTER} would've closed PR #{n}, which this PR supersedes")
+            }
+            (PostMergeAction::CloseSuperseded(n), Some(err)) => {
+                format!("{AFTER} couldn't close PR #{n}, which this PR supersedes: {err}")
+            }
+        };
+
+        let output = match (self.behaviours.plain_stdout, &outcome.error) {
+            (true, _) => msg,
+            (false, None) => msg.green().to_string(),
+            (false, Some(_)) => msg.red().to_string(),
+        };
+
+        let _ = writeln!(self.w, "        {output}");
+    }
+
+    fn branch_updated(&mut self) {
+        let msg = if self.behaviours.execute {
+            "PR's branch was behind its base; I've updated it, and will try merging it on a later run 🔄"
+        } else {
+            "PR's branch is behind its base, I would've updated it if this weren't a dry run 🔄"
+        };
+
+        let output = if self.behaviours.plain_stdout {
+            msg
+        } else {
+            &msg.blue().to_string()
+        };
+
+        let _ = writeln!(self.w, "        {output}");
+    }
+
+    fn enqueued(&mut self, enqueued: &PREnqueued) {
+        let msg = match (enqueued.strategy, self.behaviours.execute) {
+            (MergeStrategy::MergeQueue, _) if enqueued.already_handed_over => {
+                "PR matches all criteria; it's already in the merge queue 🚦 ✅"
+            }
+            (_, _) if enqueued.already_handed_over => {
+                "PR matches all criteria; auto-merge is already enabled, GitHub will merge it once it's ready 🚦 ✅"
+            }
+            (MergeStrategy::MergeQueue, true) => {
+                "PR matches all criteria; I've added it to the merge queue 🚦 ✅"
+            }
+            (MergeStrategy::MergeQueue, false) => {
+                "PR matches all criteria, I would've added it to the merge queue if this weren't a dry run ✅"
+            }
+            (_, true) => {
+                "PR matches all criteria; I've enabled auto-merge, GitHub will merge it once it's ready 🚦 ✅"
+            }
+            (_, false) => {
+                "PR matches all criteria, I would've enabled auto-merge if this weren't a dry run ✅"
+            }
+        };
+
+        let output = if self.behaviours.plain_stdout {
+            msg
+        } else {
+            &msg.green().to_string()
+        };
+
+        let _ = writeln!(self.w, "        {output}");
+    }
+
     fn error(&mut self, error: &anyhow::Error) {
         let line = format!("        error 😵: {error:#}");
         let output = if self.behaviours.plain_stdout {
@@ -1,7 +1,9 @@
 mod behaviours;
+mod discover;
 mod log;
 mod process;
 mod run;
+mod search;
 #[cfg(test)]
 mod tests;
 
@@ -1,20 +1,68 @@
-use crate::config::Config;
+use crate::config::{Config, RepoSettings};
 use crate::domain::{
-    Disqualification as DQ, GhApiQueryParam, MergeResult, PRCheck, PRDisqualified,
-    Qualification as Q, Repo, RepoCheck, RepoResult,
+    CommitContext, CommitUpgrade, Disqualification as DQ, GhApiQueryParam, HumanDuration,
+    MergeResult, MergeStrategy, PRCheck, PRCheckFinished, PRCheckInProgress, PRDisqualified,
+    PostMergeAction, Qualification as Q, Repo, RepoCheck, RepoResult,
 };
+use crate::merge::search::SearchHit;
+use crate::rule::{PrAttributes, Rule, RuleAction};
+use crate::upgrade::{UpdateBot, Upgrade, detect_bot, detect_ecosystem, release_date};
 use anyhow::Context;
+use chrono::{DateTime, TimeDelta, Utc};
 use octocrab::Octocrab;
 use octocrab::{
-    models::pulls::{MergeableState, PullRequest},
-    params::{State, repos::Commitish},
+    models::{
+        CombinedStatus, Status, StatusState,
+        checks::CheckRun,
+        issues::{Comment, Issue},
+        pulls::{MergeableState, PullRequest, Review, ReviewState},
+        repos::DiffEntry,
+    },
+    params::{
+        State,
+        pulls::State as PullRequestState,
+        repos::{Commitish, Reference},
+    },
 };
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Semaphore;
+use tokio::time::Instant;
 
 const MAX_RETRY_ATTEMPTS: usize = 3;
 const RETRY_DELAY: Duration = Duration::from_millis(3000);
+/// How many times to fetch a PR after a merge, while its mergeable state is being recomputed
+const MAX_STATE_POLLS: usize = 10;
+/// The most items GitHub's API returns per page
+const PER_PAGE: u8 = 100;
+const DEPENDABOT_REBASE_COMMENT: &str = "@dependabot rebase";
+const RENOVATE_REBASE_CHECKBOX: &str = "- [ ] <!-- rebase-check -->";
+const RENOVATE_REBASE_CHECKBOX_TICKED: &str = "- [x] <!-- rebase-check -->";
+/// How long to wait for a bot to act on a rebase request, before asking again
+const REBASE_REQUEST_EXPIRY: TimeDelta = TimeDelta::hours(24);
+const ENABLE_AUTO_MERGE_MUTATION: &str = r#"
+mutation($pullRequestId: ID!, $mergeMethod: PullRequestMergeMethod!, $expectedHeadOid: GitObjectID!, $commitHeadline: String, $commitBody: String) {
+  enablePullRequestAutoMerge(input: {pullRequestId: $pullRequestId, mergeMethod: $mergeMethod, expectedHeadOid: $expectedHeadOid, commitHeadline: $commitHeadline, commitBody: $commitBody}) {
+    clientMutationId
+  }
+}"#;
+const ENQUEUE_MUTATION: &str = r#"
+mutation($pullRequestId: ID!, $expectedHeadOid: GitObjectID!) {
+  enqueuePullRequest(input: {pullRequestId: $pullRequestId, expectedHeadOid: $expectedHeadOid}) {
+    clientMutationId
+  }
+}"#;
+const HANDED_OVER_QUERY: &str = r#"
+query($pullRequestId: ID!) {
+  node(id: $pullRequestId) {
+    ... on PullRequest {
+      autoMergeRequest {
+        enabledAt
+      }
+      isInMergeQueue
+    }
+  }
+}"#;
 
 enum MergeAttemptOutcome {
     Final(MergeResult),
@@ -22,11 +70,86 @@ enum MergeAttemptOutcome {
     // A variant for retryable errors can be added here later
 }
 
+/// What's shared by the merge attempts for all PRs of a repo.
+struct RepoContext<'a> {
+    owner: &'a str,
+    repo: &'a str,
+    client: &'a Octocrab,
+    /// The login of the user `client` acts as, if it could be looked up
+    login: Option<&'a str>,
+    /// The client to submit approving reviews with; the same as `client`, unless a separate token
+    /// has been provided for approvals
+    approver: &'a Octocrab,
+    /// The login of the user `approver` acts as, if it could be looked up
+    approver_login: Option<&'a str>,
+    /// The freeze label, and the PR that has it, if merges for the repo are frozen
+    frozen_by: Option<&'a (String, u64)>,
+    /// The maximum number of pages to fetch when listing a PR's checks, changed files, reviews,
+    /// and comments
+    max_pages: u32,
+    /// The PRs being looked at for the repo; a merged PR can supersede some of them
+    open_prs: &'a [PullRequest],
+    execute: bool,
+}
+
+/// A check run, or a commit status; either way, its conclusion is `None` while it's pending.
+#[derive(Debug)]
+struct Check {
+    name: String,
+    conclusion: Option<String>,
+}
+
+impl Check {
+    fn from_check_run(check_run: &CheckRun) -> Self {
+        Self {
+            name: check_run.name.clone(),
+            conclusion: check_run.conclusion.as_deref().map(str::to_lowercase),
+        }
+    }
+
+    /// Returns `None` for statuses without a context, as there's no way to refer to them.
+    fn from_status(status: &Status) -> Option<Self> {
+        let conclusion = match status.state {
+            StatusState::Success => Some("success"),
+            StatusState::Failure => Some("failure"),
+            StatusState::Error => Some("error"),
+            StatusState::Pending => None,
+            _ => Some("unknown"),
+        };
+
+        Some(Self {
+            name: status.context.clone()?,
+            conclusion: conclusion.map(str::to_string),
+        })
+    }
+}
+
+/// The clients to talk to GitHub with, and the logins of the users they act as, for recognising
+/// what mrj did on earlier runs; a login is `None` if it couldn't be looked up (GitHub App tokens
+/// can't look up their user).
+#[derive(Clone)]
+pub(super) struct Clients {
+    pub(super) client: Arc<Octocrab>,
+    pub(super) login: Option<String>,
+    /// The client to submit approving reviews with; see [`RepoContext::approver`]
+    pub(super) approver: Arc<Octocrab>,
+    pub(super) approver_login: Option<String>,
+}
+
+/// Where to get the PRs to consider for a repo from.
+pub(super) enum Candidates {
+    /// All open PRs of the repo
+    OpenPRs,
+    /// Specific PRs, as found via a search query
+    Found(Vec<SearchHit>),
+}
+
 pub(super) async fn merge_pr_for_repo(
     semaphore: Arc<Semaphore>,
-    client: Arc<Octocrab>,
+    clients: Clients,
     config: &Config,
     repo: Repo,
+    candidates: Candidates,
     execute: bool,
 ) -> RepoResult {
     let mut repo_check = RepoCheck::new(&repo.owner, &repo.repo);
@@ -42,6 +165,102 @@ pub(super) async fn merge_pr_for_repo(
         }
     }
 
+    let settings = config.settings_for(&repo);
+    let client = clients.client.as_ref();
+
+    let prs_fetched = matches!(candidates, Candidates::Found(_));
+    let pull_requests = match candidates {
+        Candidates::OpenPRs => list_open_prs(client, config, &repo, &settings).await,
+        Candidates::Found(hits) => get_prs(client, config, &repo, &settings, &hits).await,
+    };
+
+    let pull_requests = match pull_requests {
+        Ok(p) => p,
+        Err(err) => {
+            return RepoResult::Errored(repo_check.record_error(err));
+        }
+    };
+
+    if pull_requests.is_empty() {
+        return RepoResult::Finished(repo_check.finish());
+    }
+
+    let frozen_by = match &settings.freeze_label {
+        Some(label) => match find_freeze(client, &repo, label).await {
+            Ok(frozen_by) => frozen_by,
+            Err(err) => {
+                return RepoResult::Errored(repo_check.record_error(err));
+            }
+        },
+        None => None,
+    };
+
+    let ctx = RepoContext {
+        owner: &repo.owner,
+        repo: &repo.repo,
+        client,
+        login: clients.login.as_deref(),
+        approver: clients.approver.as_ref(),
+        approver_login: clients.approver_login.as_deref(),
+        frozen_by: frozen_by.as_ref(),
+        max_pages: config.max_pages,
+        open_prs: &pull_requests,
+        execute,
+    };
+
+    let mut num_merged = 0;
+    let mut closed = vec![];
+    for pull_request in &pull_requests {
+        // closed as superseded by a PR merged earlier in the run
+        if closed.contains(&pull_request.number) {
+            continue;
+        }
+
+        let ecosystem = detect_ecosystem(
+            pull_request.title.as_deref().unwrap_or_default(),
+            pull_request.body.as_deref(),
+            &pull_request.head.ref_field,
+        );
+        let pr_settings =
+            config.settings_for_pr(&repo, &pull_request.base.ref_field, ecosystem.as_deref());
+
+        // fetched PRs are up to date until something gets merged
+        let fetched = prs_fetched && num_merged == 0;
+        let merge_result =
+            merge_pr_with_retry(&ctx, pull_request, fetched, &pr_settings, num_merged + 1).await;
+        let takes_merge_slot = merge_result.takes_merge_slot();
+        if let MergeResult::Qualified(pr_check) = &merge_result {
+            closed.extend(pr_check.state.post_merge.iter().filter_map(|outcome| {
+                match (&outcome.action, &outcome.error) {
+                    (PostMergeAction::CloseSuperseded(number), None) => Some(*number),
+                    _ => None,
+                }
+            }));
+        }
+        repo_check.add_merge_result(merge_result);
+
+        if takes_merge_slot {
+            num_merged += 1;
+            if num_merged >= settings.max_merges_per_repo {
+                break;
+            }
+
+            // gives CI some breathing room; the next PR's mergeable state is waited for anyway
+            if execute && let Some(delay) = &settings.merge_delay {
+                tokio::time::sleep(delay.duration).await;
+            }
+        }
+    }
+
+    RepoResult::Finished(repo_check.finish())
+}
+
+async fn list_open_prs(
+    client: &Octocrab,
+    config: &Config,
+    repo: &Repo,
+    settings: &RepoSettings,
+) -> anyhow::Result<Vec<PullRequest>> {
     let pulls = client.pulls(&repo.owner, &repo.repo);
 
     let mut page_builder = pulls
@@ -49,54 +268,140 @@ pub(super) async fn merge_pr_for_repo(
         .state(State::Open)
         .sort(config.sort_by.to_gh_api())
         .direction(config.sort_direction.to_gh_api())
-        .per_page(100);
+        .per_page(PER_PAGE);
 
-    if let Some(base_branch) = &config.base_branch {
+    if let Some(base_branch) = &settings.base_branch {
         page_builder = page_builder.base(base_branch);
     }
 
-    let page = match page_builder.send().await.context("couldn't get PRs") {
-        Ok(p) => p,
-        Err(err) => {
-            return RepoResult::Errored(repo_check.record_error(err));
-        }
-    };
+    let mut page = page_builder.send().await.context("couldn't get PRs")?;
+    let mut pull_requests = std::mem::take(&mut page.items);
 
-    if page.items.is_empty() {
-        return RepoResult::Finished(repo_check.finish());
+    // PRs beyond the cap are left for the next run
+    for _ in 1..config.max_pages {
+        match client
+            .get_page::<PullRequest>(&page.next)
+            .await
+            .context("couldn't get next page of PRs")?
+        {
+            Some(mut next_page) => {
+                pull_requests.append(&mut next_page.items);
+                page = next_page;
+            }
+            None => break,
+        }
     }
 
-    for pull_request in &page {
-        let merge_result = merge_pr_with_retry(
-            &repo.owner,
-            &repo.repo,
-            pull_request,
-            client.as_ref(),
-            config,
-            execute,
-        )
-        .await;
-        let no_failure = merge_result.no_failure();
-        repo_check.add_merge_result(merge_result);
+    // the API only filters by a single base branch, so patterns are matched here
+    pull_requests.retain(|pr| base_allowed(settings, &pr.base.ref_field));
 
-        if no_failure {
-            break;
+    Ok(pull_requests)
+}
+
+/// Fetches the PRs found via a search query, leaving out the ones the search results already rule
+/// out. Fetching a PR gets everything evaluating it needs, so `merge_pr` only fetches it again once
+/// a merge may have changed its mergeable state.
+async fn get_prs(
+    client: &Octocrab,
+    config: &Config,
+    repo: &Repo,
+    settings: &RepoSettings,
+    hits: &[SearchHit],
+) -> anyhow::Result<Vec<PullRequest>> {
+    let pulls = client.pulls(&repo.owner, &repo.repo);
+    let mut pull_requests = vec![];
+
+    for hit in hits
+        .iter()
+        .filter(|hit| could_qualify(config, settings, hit))
+    {
+        let number = hit.number;
+        let pr = pulls
+            .get(number)
+            .await
+            .with_context(|| format!("couldn't get PR #{number}"))?;
+
+        // the base branch isn't part of search results
+        if let Some(base_branch) = &settings.base_branch
+            && &pr.base.ref_field != base_branch
+        {
+            continue;
+        }
+
+        if !base_allowed(settings, &pr.base.ref_field) {
+            continue;
         }
+
+        pull_requests.push(pr);
     }
 
-    RepoResult::Finished(repo_check.finish())
+    Ok(pull_requests)
+}
+
+/// Whether a search hit is worth fetching. Base branch and ecosystem overrides are only known once
+/// a PR has been fetched, so an author trusted by any of them keeps the PR in.
+fn could_qualify(config: &Config, settings: &RepoSettings, hit: &SearchHit) -> bool {
+    // search queries can match PRs that listing open PRs wouldn't return
+    if !hit.open {
+        return false;
+    }
+
+    settings.trusted_authors.contains(&hit.author)
+        || config
+            .base_overrides
+            .iter()
+            .map(|(_, o)| o)
+            .chain(config.ecosystem_overrides.values())
+            .filter_map(|o| o.trusted_authors.as_ref())
+            .any(|authors| authors.contains(&hit.author))
+}
+
+/// Looks for an open PR with the freeze label. The PRs being looked at can't be relied on for this:
+/// a search query, or a listing cut short by `max_pages`, can easily leave out the PR with the
+/// label.
+async fn find_freeze(
+    client: &Octocrab,
+    repo: &Repo,
+    label: &str,
+) -> anyhow::Result<Option<(String, u64)>> {
+    let labels = [label.to_string()];
+    let page = client
+        .issues(&repo.owner, &repo.repo)
+        .list()
+        .state(State::Open)
+        .labels(&labels)
+        .per_page(PER_PAGE)
+        .send()
+        .await
+        .context("couldn't look for PRs with the freeze label")?;
+
+    Ok(freezing_pr(&page.items).map(|number| (label.to_string(), number)))
+}
+
+/// The issues API lists PRs as well; only a PR freezes merges.
+fn freezing_pr(issues: &[Issue]) -> Option<u64> {
+    issues
+        .iter()
+        .find(|issue| issue.pull_request.is_some())
+        .map(|issue| issue.number)
 }
 
+fn base_allowed(settings: &RepoSettings, base: &str) -> bool {
+    settings.base_branches.is_empty() || settings.base_branches.iter().any(|b| b.is_match(base))
+}
+
+/// `fetched` tells whether `pull_request` has been fetched in full, and is up to date; if so, the
+/// first attempt doesn't fetch it again.
 async fn merge_pr_with_retry(
-    owner: &str,
-    repo: &str,
+    ctx: &RepoContext<'_>,
     pull_request: &PullRequest,
-    client: &Octocrab,
-    config: &Config,
-    execute: bool,
+    fetched: bool,
+    settings: &RepoSettings,
+    slot: usize,
 ) -> MergeResult {
     for attempt in 1..=MAX_RETRY_ATTEMPTS {
-        match merge_pr(owner, repo, pull_request, client, config, execute).await {
+        let fetched = fetched && attempt == 1;
+        match merge_pr(ctx, pull_request, fetched, settings, slot).await {
             MergeAttemptOutcome::Final(result) => return result,
             MergeAttemptOutcome::Retryable(pr_check) => {
                 if attempt == MAX_RETRY_ATTEMPTS {
@@ -112,16 +417,21 @@ async fn merge_pr_with_retry(
 }
 
 async fn merge_pr(
-    owner: &str,
-    repo: &str,
+    ctx: &RepoContext<'_>,
     pull_request: &PullRequest,
-    client: &Octocrab,
-    config: &Config,
-    execute: bool,
+    fetched: bool,
+    settings: &RepoSettings,
+    slot: usize,
 ) -> MergeAttemptOutcome {
+    let (owner, repo, client) = (ctx.owner, ctx.repo, ctx.client);
     let mut pr_check = PRCheck::from(pull_request);
 
-    if let Some(head_pattern) = &config.head_pattern {
+    // PRs against other base branches are filtered out when listing
+    if !settings.base_branches.is_empty() {
+        pr_check.add_qualification(Q::Base(pull_request.base.ref_field.clone()));
+    }
+
+    if let Some(head_pattern) = &settings.head_pattern {
         let head_ref = pull_request.head.ref_field.clone();
         if head_pattern.re.is_match(&head_ref) {
             pr_check.add_qualification(Q::Head(head_ref));
@@ -133,7 +443,7 @@ async fn merge_pr(
     }
 
     match &pull_request.user {
-        Some(trusted_user) if config.trusted_authors.contains(&trusted_user.login) => {
+        Some(trusted_user) if settings.trusted_authors.contains(&trusted_user.login) => {
             pr_check.add_qualification(Q::Author(trusted_user.login.clone()));
         }
         Some(other_user) => {
@@ -148,77 +458,270 @@ async fn merge_pr(
         }
     }
 
-    let pr = match client
-        .pulls(owner, repo)
-        .get(pull_request.number)
-        .await
-        .context("couldn't get details")
+    if settings.skip_drafts && pull_request.draft.unwrap_or(false) {
+        return MergeAttemptOutcome::Final(MergeResult::Disqualified(
+            pr_check.disqualify(DQ::Draft),
+        ));
+    }
+
+    if let Some(max_pr_age) = &settings.max_pr_age
+        && let Some(created_at) = pull_request.created_at
+        && let Err(dq) = evaluate_staleness(max_pr_age, created_at, Utc::now())
     {
-        Ok(pr) => pr,
-        Err(err) => {
-            return MergeAttemptOutcome::Final(MergeResult::Errored(pr_check.record_error(err)));
+        return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(dq)));
+    }
+
+    let labels = pr_labels(pull_request).collect::<Vec<_>>();
+    match evaluate_labels(settings, &labels, ctx.frozen_by) {
+        Ok(qualifications) => {
+            for q in qualifications {
+                pr_check.add_qualification(q);
+            }
+        }
+        Err(dq) => {
+            return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(dq)));
+        }
+    }
+
+    if !settings.ecosystems.is_empty() {
+        match &pr_check.ecosystem {
+            Some(e) if settings.ecosystems.contains(e) => {
+                let ecosystem = e.clone();
+                pr_check.add_qualification(Q::Ecosystem(ecosystem));
+            }
+            other => {
+                let ecosystem = other.clone();
+                return MergeAttemptOutcome::Final(MergeResult::Disqualified(
+                    pr_check.disqualify(DQ::Ecosystem(ecosystem)),
+                ));
+            }
+        }
+    }
+
+    match evaluate_package(settings, pr_check.upgrade.as_ref()) {
+        Some(Ok(q)) => pr_check.add_qualification(q),
+        Some(Err(dq)) => {
+            return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(dq)));
+        }
+        None => {}
+    }
+
+    if let Some(allowed_update_types) = &settings.allowed_update_types {
+        let package = pr_check.upgrade.as_ref().map(|u| u.package.clone());
+        let update_type = pr_check.upgrade.as_ref().and_then(|u| u.update_type());
+
+        match (package, update_type) {
+            (Some(package), Some(update_type)) if allowed_update_types.contains(&update_type) => {
+                pr_check.add_qualification(Q::UpdateType {
+                    package,
+                    update_type,
+                });
+            }
+            (package, update_type) => {
+                return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(
+                    DQ::UpdateType {
+                        package,
+                        update_type,
+                    },
+                )));
+            }
+        }
+    }
+
+    if let Some(min_pr_age) = &settings.min_pr_age {
+        let released_at = pr_check.upgrade.as_ref().and_then(|u| {
+            pull_request
+                .body
+                .as_deref()
+                .and_then(|body| release_date(body, &u.to))
+        });
+
+        let pushed_at = match last_pushed_at(client, owner, repo, pull_request).await {
+            Ok(pushed_at) => pushed_at,
+            Err(err) => {
+                return MergeAttemptOutcome::Final(MergeResult::Errored(
+                    pr_check.record_error(err),
+                ));
+            }
+        };
+
+        let aged_since = cool_down_start(pushed_at, released_at);
+        if let Err(dq) = evaluate_pr_age(min_pr_age, aged_since, Utc::now()) {
+            return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(dq)));
+        }
+    }
+
+    // a slot past the first means something was merged earlier in the run, after which GitHub
+    // recomputes the mergeable state of the remaining PRs
+    let after_merge = slot > 1;
+
+    let mut pr = if fetched {
+        pull_request.clone()
+    } else {
+        match get_pr_once_state_known(ctx, pull_request.number, after_merge).await {
+            Ok(pr) => pr,
+            Err(err) => {
+                return MergeAttemptOutcome::Final(MergeResult::Errored(
+                    pr_check.record_error(err),
+                ));
+            }
         }
     };
 
-    let pr_head_ref = pr.head.sha;
+    if let Err(dq) = evaluate_diff_size(settings, pr.changed_files, pr.additions) {
+        return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(dq)));
+    }
 
-    let checks = match client
-        .checks(owner, repo)
-        .list_check_runs_for_git_ref(Commitish::from(pr_head_ref.clone()))
-        .send()
-        .await
-        .context("couldn't get pr checks")
-    {
+    let needs_paths =
+        !settings.allowed_paths.is_empty() || settings.rules.iter().any(|r| r.rule.uses_files());
+    let paths = if needs_paths {
+        match list_changed_paths(ctx, pr.number, pr.changed_files).await {
+            Ok(p) => p,
+            Err(err) => {
+                return MergeAttemptOutcome::Final(MergeResult::Errored(
+                    pr_check.record_error(err),
+                ));
+            }
+        }
+    } else {
+        vec![]
+    };
+
+    if !settings.allowed_paths.is_empty() {
+        match evaluate_paths(settings, &paths) {
+            Ok(q) => pr_check.add_qualification(q),
+            Err(dq) => {
+                return MergeAttemptOutcome::Final(MergeResult::Disqualified(
+                    pr_check.disqualify(dq),
+                ));
+            }
+        }
+    }
+
+    // everything below is evaluated against this commit, and the merge is pinned to it
+    let evaluated_sha = pr.head.sha.clone();
+
+    let (checks, waited) = match list_checks(ctx, &evaluated_sha, settings).await {
         Ok(c) => c,
         Err(err) => {
             return MergeAttemptOutcome::Final(MergeResult::Errored(pr_check.record_error(err)));
         }
     };
 
-    for check in &checks.check_runs {
-        match check.conclusion.as_deref() {
-            Some("success") => {
-                pr_check.add_qualification(Q::Check {
-                    name: check.name.clone(),
-                    conclusion: "success".to_string(),
-                });
-            }
-            Some("skipped") => {
-                if config.merge_if_checks_skipped {
-                    pr_check.add_qualification(Q::Check {
-                        name: check.name.clone(),
-                        conclusion: "success".to_string(),
-                    });
-                } else {
-                    return MergeAttemptOutcome::Final(MergeResult::Disqualified(
-                        pr_check.disqualify(DQ::Check {
-                            name: check.name.clone(),
-                            conclusion: Some("skipped".to_string()),
-                        }),
-                    ));
-                }
+    // the mergeable state is likely to have changed while checks were running
+    if waited {
+        pr = match client
+            .pulls(owner, repo)
+            .get(pull_request.number)
+            .await
+            .context("couldn't get details")
+        {
+            Ok(pr) => pr,
+            Err(err) => {
+                return MergeAttemptOutcome::Final(MergeResult::Errored(
+                    pr_check.record_error(err),
+                ));
             }
-            Some("neutral") if config.merge_if_checks_neutral => {
-                pr_check.add_qualification(Q::Check {
-                    name: check.name.clone(),
-                    conclusion: "neutral".to_string(),
-                });
+        };
+
+        if pr.head.sha != evaluated_sha {
+            return MergeAttemptOutcome::Retryable(pr_check.disqualify(DQ::HeadChanged));
+        }
+    }
+
+    let (ignored_checks, check_runs): (Vec<_>, Vec<_>) = checks
+        .iter()
+        .partition(|check| is_check_ignored(settings, &check.name));
+
+    for check in ignored_checks {
+        pr_check.add_qualification(Q::IgnoredCheck {
+            name: check.name.clone(),
+            conclusion: check.conclusion.clone(),
+        });
+    }
+
+    let check_names = check_runs
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect::<Vec<_>>();
+    if let Err(dq) = evaluate_check_presence(settings, &check_names) {
+        return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(dq)));
+    }
+
+    match evaluate_checks(settings, &check_runs) {
+        Ok(qualifications) => {
+            for q in qualifications {
+                pr_check.add_qualification(q);
             }
-            Some(non_successful_conclusion) => {
-                return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(
-                    DQ::Check {
-                        name: check.name.clone(),
-                        conclusion: Some(non_successful_conclusion.to_lowercase()),
-                    },
+        }
+        Err(dq) => {
+            return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(dq)));
+        }
+    }
+
+    // rules are evaluated once everything they can refer to is known
+    let attributes = rule_attributes(pull_request, &pr, &pr_check, paths, &check_runs, Utc::now());
+    match evaluate_rules(&settings.rules, &attributes) {
+        Some(Ok(q)) => pr_check.add_qualification(q),
+        Some(Err(dq)) => {
+            return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(dq)));
+        }
+        None => {}
+    }
+
+    // GitHub only needs a PR handed over once; one that already is doesn't use up a merge slot,
+    // and isn't approved again on every run while it waits
+    if !settings.merge_strategy.is_direct() {
+        let Some(node_id) = pr.node_id.as_deref() else {
+            return MergeAttemptOutcome::Final(MergeResult::Errored(
+                pr_check.record_error(anyhow::anyhow!("GitHub didn't return the PR's node ID")),
+            ));
+        };
+
+        match already_handed_over(client, node_id).await {
+            Ok(false) => {}
+            Ok(true) => {
+                return MergeAttemptOutcome::Final(MergeResult::Enqueued(pr_check.enqueue(
+                    settings.merge_strategy,
+                    evaluated_sha,
+                    true,
                 )));
             }
+            Err(err) => {
+                return MergeAttemptOutcome::Final(MergeResult::Errored(
+                    pr_check.record_error(err),
+                ));
+            }
+        }
+    }
+
+    let mut needs_approval = false;
+    let mut blocked_until_approved = false;
+    if settings.require_approval || settings.auto_approve {
+        let reviews = match list_reviews(ctx, pr.number).await {
+            Ok(r) => r,
+            Err(err) => {
+                return MergeAttemptOutcome::Final(MergeResult::Errored(
+                    pr_check.record_error(err),
+                ));
+            }
+        };
+
+        match approved_by(settings, &reviews, &evaluated_sha) {
+            Some(reviewer) => pr_check.add_qualification(Q::Approval(reviewer)),
+            // approved on an earlier run that didn't end up merging the PR
+            None if settings.auto_approve
+                && ctx
+                    .approver_login
+                    .is_some_and(|login| has_approved(&reviews, login, &evaluated_sha)) =>
+            {
+                pr_check.add_qualification(Q::AutoApproval);
+            }
+            None if settings.auto_approve => needs_approval = true,
             None => {
-                return MergeAttemptOutcome::Final(MergeResult::Disqualified(pr_check.disqualify(
-                    DQ::Check {
-                        name: check.name.clone(),
-                        conclusion: None,
-                    },
-                )));
+                return MergeAttemptOutcome::Final(MergeResult::Disqualified(
+                    pr_check.disqualify(DQ::ApprovalMissing),
+                ));
             }
         }
     }
@@ -228,9 +731,50 @@ async fn merge_pr(
             MergeableState::Clean => {
                 pr_check.add_qualification(Q::State("clean".to_string()));
             }
-            MergeableState::Blocked if config.merge_if_blocked => {
+            MergeableState::Blocked if settings.merge_if_blocked => {
+                pr_check.add_qualification(Q::State("blocked".to_string()));
+            }
+            // pending checks block a PR (or make it unstable); GitHub waits for them when
+            // merging via auto-merge or a merge queue
+            MergeableState::Blocked if !settings.merge_strategy.is_direct() => {
                 pr_check.add_qualification(Q::State("blocked".to_string()));
             }
+            // the missing review may be what blocks the PR; whether it is can only be told
+            // once the PR has been approved
+            MergeableState::Blocked if needs_approval => blocked_until_approved = true,
+            MergeableState::Unstable if !settings.merge_strategy.is_direct() => {
+                pr_check.add_qualification(Q::State("unstable".to_string()));
+            }
+            // updating the branch re-triggers checks, so merging is left for a later run; a branch
+            // that a merge earlier in the run put behind is updated regardless
+            MergeableState::Behind if settings.update_behind_branches || after_merge => {
+                if ctx.execute
+                    && let Err(err) = update_branch(client, owner, repo, pr.number).await
+                {
+                    return MergeAttemptOutcome::Final(MergeResult::Errored(
+                        pr_check.record_error(err),
+                    ));
+                }
+
+                return MergeAttemptOutcome::Final(MergeResult::BranchUpdated(
+                    pr_check.mark_branch_updated(),
+                ));
+            }
+            MergeableState::Dirty if settings.request_rebases => {
+                let dq = match request_rebase(ctx, &pr).await {
+                    Ok(Some(dq)) => dq,
+                    Ok(None) => DQ::State(Some("dirty".to_string())),
+                    Err(err) => {
+                        return MergeAttemptOutcome::Final(MergeResult::Errored(
+                            pr_check.record_error(err),
+                        ));
+                    }
+                };
+
+                return MergeAttemptOutcome::Final(MergeResult::Disqualified(
+                    pr_check.disqualify(dq),
+                ));
+            }
             MergeableState::Unknown => {
                 return MergeAttemptOutcome::Retryable(
                     pr_check.disqualify(DQ::State(Some("unknown".to_string()))),
@@ -247,17 +791,2460 @@ async fn merge_pr(
         }
     }
 
-    if execute
-        && let Err(err) = client
+    if needs_approval {
+        if ctx.execute
+            && let Err(err) = approve_pr(ctx.approver, owner, repo, pr.number, &evaluated_sha).await
+        {
+            return MergeAttemptOutcome::Final(MergeResult::Errored(pr_check.record_error(err)));
+        }
+
+        pr_check.add_qualification(Q::AutoApproval);
+    }
+
+    // during dry runs, there's no approval to tell whether it unblocks the PR
+    if blocked_until_approved && !ctx.execute {
+        pr_check.add_qualification(Q::State("blocked".to_string()));
+    } else if blocked_until_approved {
+        pr = match client
             .pulls(owner, repo)
-            .merge(pr.number)
-            .method(config.merge_type.merge_method())
-            .send()
+            .get(pull_request.number)
             .await
-            .context("couldn't merge PR")
-    {
-        return MergeAttemptOutcome::Final(MergeResult::Errored(pr_check.record_error(err)));
+            .context("couldn't get details")
+        {
+            Ok(pr) => pr,
+            Err(err) => {
+                return MergeAttemptOutcome::Final(MergeResult::Errored(
+                    pr_check.record_error(err),
+                ));
+            }
+        };
+
+        if pr.head.sha != evaluated_sha {
+            return MergeAttemptOutcome::Retryable(pr_check.disqualify(DQ::HeadChanged));
+        }
+
+        match pr.mergeable_state.as_ref() {
+            Some(MergeableState::Clean) => {
+                pr_check.add_qualification(Q::State("clean".to_string()));
+            }
+            // GitHub is yet to take the approval into account; the next attempt recognises it as
+            // mrj's own, so the PR doesn't get approved again
+            Some(MergeableState::Unknown) | None => {
+                return MergeAttemptOutcome::Retryable(
+                    pr_check.disqualify(DQ::State(Some("unknown".to_string()))),
+                );
+            }
+            // something other than the missing review blocks the PR
+            Some(other) => {
+                return MergeAttemptOutcome::Final(MergeResult::Disqualified(
+                    pr_check.disqualify(DQ::State(Some(format!("{other:?}").to_lowercase()))),
+                ));
+            }
+        }
+    }
+
+    if settings.max_merges_per_repo > 1 {
+        pr_check.add_qualification(Q::MergeSlot {
+            slot,
+            max: settings.max_merges_per_repo,
+        });
     }
 
-    MergeAttemptOutcome::Final(MergeResult::Qualified(pr_check.finish()))
+    // rendered even during dry runs, so that broken templates surface early
+    let (commit_title, commit_message) = match render_commit_text(settings, &pr, &pr_check) {
+        Ok(text) => text,
+        Err(err) => {
+            return MergeAttemptOutcome::Final(MergeResult::Errored(pr_check.record_error(err)));
+        }
+    };
+
+    if let Some(node_id) = pr.node_id.as_deref()
+        && !settings.merge_strategy.is_direct()
+    {
+        if ctx.execute
+            && let Err(err) = hand_over_to_github(
+                client,
+                settings,
+                node_id,
+                &evaluated_sha,
+                commit_title,
+                commit_message,
+            )
+            .await
+        {
+            return MergeAttemptOutcome::Final(MergeResult::Errored(pr_check.record_error(err)));
+        }
+
+        return MergeAttemptOutcome::Final(MergeResult::Enqueued(pr_check.enqueue(
+            settings.merge_strategy,
+            evaluated_sha,
+            false,
+        )));
+    }
+
+    let mut merge_commit_sha = None;
+    if ctx.execute {
+        let pulls = client.pulls(owner, repo);
+        let mut merge = pulls
+            .merge(pr.number)
+            .method(settings.merge_type.merge_method())
+            .sha(evaluated_sha.clone());
+        if let Some(title) = commit_title {
+            merge = merge.title(title);
+        }
+        if let Some(message) = commit_message {
+            merge = merge.message(message);
+        }
+
+        match merge.send().await {
+            Ok(merged) => merge_commit_sha = merged.sha,
+            // GitHub responds with a 409 when the head no longer matches the sha provided
+            Err(octocrab::Error::GitHub { source, .. }) if source.status_code.as_u16() == 409 => {
+                return MergeAttemptOutcome::Retryable(pr_check.disqualify(DQ::HeadChanged));
+            }
+            Err(err) => {
+                return MergeAttemptOutcome::Final(MergeResult::Errored(
+                    pr_check.record_error(anyhow::Error::from(err).context("couldn't merge PR")),
+                ));
+            }
+        }
+    }
+
+    let mut pr_check = pr_check.finish(evaluated_sha, merge_commit_sha);
+    run_post_merge_actions(ctx, settings, &pr, &mut pr_check).await;
+
+    MergeAttemptOutcome::Final(MergeResult::Qualified(pr_check))
+}
+
+/// Fetches a PR; if `poll` is set, keeps fetching it while GitHub is yet to compute its mergeable
+/// state, up to `MAX_STATE_POLLS` times, after which the state is left unknown.
+async fn get_pr_once_state_known(
+    ctx: &RepoContext<'_>,
+    number: u64,
+    poll: bool,
+) -> anyhow::Result<PullRequest> {
+    let pulls = ctx.client.pulls(ctx.owner, ctx.repo);
+
+    for _ in 1..MAX_STATE_POLLS {
+        let pr = pulls.get(number).await.context("couldn't get details")?;
+        if !poll || !matches!(pr.mergeable_state, None | Some(MergeableState::Unknown)) {
+            return Ok(pr);
+        }
+
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+
+    pulls.get(number).await.context("couldn't get details")
+}
+
+/// Deletes the merged PR's head branch, and closes the PRs it supersedes, if configured to; during
+/// dry runs, the actions are only recorded. A failed action doesn't affect the merge.
+async fn run_post_merge_actions(
+    ctx: &RepoContext<'_>,
+    settings: &RepoSettings,
+    pr: &PullRequest,
+    pr_check: &mut PRCheck<PRCheckFinished>,
+) {
+    // branches of forks can't be deleted
+    let head_repo = pr.head.repo.as_ref().and_then(|r| r.full_name.as_deref());
+    if settings.delete_branch_after_merge
+        && head_repo == Some(format!("{}/{}", ctx.owner, ctx.repo).as_str())
+    {
+        let branch = pr.head.ref_field.clone();
+        let error = if ctx.execute {
+            delete_branch(ctx, &branch).await.err()
+        } else {
+            None
+        };
+        pr_check.add_post_merge_outcome(
+            PostMergeAction::DeleteBranch(branch),
+            error.map(|err| format!("{err:#}")),
+        );
+    }
+
+    if !settings.close_superseded {
+        return;
+    }
+
+    let Some(upgrade) = pr_check.upgrade.clone() else {
+        return;
+    };
+
+    let merged = UpgradePr::new(pr, &upgrade);
+    let superseded = ctx
+        .open_prs
+        .iter()
+        .filter(|other| {
+            other.number != pr.number
+                && Upgrade::parse(
+                    other.title.as_deref().unwrap_or_default(),
+                    other.body.as_deref(),
+                    &other.head.ref_field,
+                )
+                .is_some_and(|u| supersedes(settings, &merged, &UpgradePr::new(other, &u)))
+        })
+        .map(|other| other.number)
+        .collect::<Vec<_>>();
+
+    for number in superseded {
+        let error = if ctx.execute {
+            close_superseded_pr(ctx, number, pr.number).await.err()
+        } else {
+            None
+        };
+        pr_check.add_post_merge_outcome(
+            PostMergeAction::CloseSuperseded(number),
+            error.map(|err| format!("{err:#}")),
+        );
+    }
+}
+
+/// A PR that upgrades a package, with what's needed to tell whether it supersedes another one.
+struct UpgradePr<'a> {
+    upgrade: &'a Upgrade,
+    base: &'a str,
+    head: &'a str,
+    author: Option<&'a str>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl<'a> UpgradePr<'a> {
+    fn new(pr: &'a PullRequest, upgrade: &'a Upgrade) -> Self {
+        Self {
+            upgrade,
+            base: &pr.base.ref_field,
+            head: &pr.head.ref_field,
+            author: pr.user.as_ref().map(|u| u.login.as_str()),
+            created_at: pr.created_at,
+        }
+    }
+}
+
+/// Whether the `merged` PR supersedes the `other` one: both upgrade the same package (in the same
+/// ecosystem and directory) against the same base, the other PR is older and doesn't upgrade to a
+/// newer version, and it was opened by a trusted author.
+fn supersedes(settings: &RepoSettings, merged: &UpgradePr, other: &UpgradePr) -> bool {
+    merged.upgrade.package == other.upgrade.package
+        && merged.upgrade.ecosystem == other.upgrade.ecosystem
+        && merged.base == other.base
+        && head_prefix(merged.head) == head_prefix(other.head)
+        && other.upgrade.to.at_most(&merged.upgrade.to)
+        && other
+            .author
+            .is_some_and(|a| settings.trusted_authors.iter().any(|t| t == a))
+        && matches!((merged.created_at, other.created_at), (Some(m), Some(o)) if o < m)
+}
+
+/// The head ref without its last segment; bots put the directory being updated in here, eg.
+/// "dependabot/npm_and_yarn/frontend".
+fn head_prefix(head: &str) -> &str {
+    head.rsplit_once('/').map_or("", |(prefix, _)| prefix)
+}
+
+async fn delete_branch(ctx: &RepoContext<'_>, branch: &str) -> anyhow::Result<()> {
+    ctx.client
+        .repos(ctx.owner, ctx.repo)
+        .delete_ref(&Reference::Branch(branch.to_string()))
+        .await
+        .context("couldn't delete PR's branch")
+}
+
+async fn close_superseded_pr(
+    ctx: &RepoContext<'_>,
+    number: u64,
+    merged_number: u64,
+) -> anyhow::Result<()> {
+    ctx.client
+        .issues(ctx.owner, ctx.repo)
+        .create_comment(
+            number,
+            format!("Superseded by #{merged_number}, which mrj has merged; closing this PR."),
+        )
+        .await
+        .context("couldn't comment on superseded PR")?;
+
+    ctx.client
+        .pulls(ctx.owner, ctx.repo)
+        .update(number)
+        .state(PullRequestState::Closed)
+        .send()
+        .await
+        .context("couldn't close superseded PR")?;
+
+    Ok(())
+}
+
+/// Whether auto-merge has already been enabled on a PR, or it's already in the merge queue.
+async fn already_handed_over(client: &Octocrab, node_id: &str) -> anyhow::Result<bool> {
+    let payload = serde_json::json!({
+        "query": HANDED_OVER_QUERY,
+        "variables": {
+            "pullRequestId": node_id,
+        },
+    });
+
+    let response: serde_json::Value = client
+        .graphql(&payload)
+        .await
+        .context("couldn't check whether the PR has been handed over to GitHub")?;
+
+    if let Some(errors) = graphql_errors(&response) {
+        anyhow::bail!("couldn't check whether the PR has been handed over to GitHub: {errors}");
+    }
+
+    Ok(is_handed_over(&response))
+}
+
+fn is_handed_over(response: &serde_json::Value) -> bool {
+    let pr = &response["data"]["node"];
+
+    !pr["autoMergeRequest"].is_null() || pr["isInMergeQueue"].as_bool().unwrap_or_default()
+}
+
+/// Enables auto-merge on a PR, or adds it to the merge queue, as per the merge strategy; either way
+/// GitHub only merges the PR if its head is still the evaluated commit.
+async fn hand_over_to_github(
+    client: &Octocrab,
+    settings: &RepoSettings,
+    node_id: &str,
+    sha: &str,
+    commit_title: Option<String>,
+    commit_message: Option<String>,
+) -> anyhow::Result<()> {
+    let (payload, action) = match settings.merge_strategy {
+        MergeStrategy::Direct => return Ok(()),
+        MergeStrategy::AutoMerge => (
+            serde_json::json!({
+                "query": ENABLE_AUTO_MERGE_MUTATION,
+                "variables": {
+                    "pullRequestId": node_id,
+                    "mergeMethod": settings.merge_type.graphql_merge_method(),
+                    "expectedHeadOid": sha,
+                    "commitHeadline": commit_title,
+                    "commitBody": commit_message,
+                },
+            }),
+            "enable auto-merge",
+        ),
+        // the merge queue's own settings decide how PRs get merged
+        MergeStrategy::MergeQueue => (
+            serde_json::json!({
+                "query": ENQUEUE_MUTATION,
+                "variables": {
+                    "pullRequestId": node_id,
+                    "expectedHeadOid": sha,
+                },
+            }),
+            "add PR to the merge queue",
+        ),
+    };
+
+    let response: serde_json::Value = client
+        .graphql(&payload)
+        .await
+        .with_context(|| format!("couldn't {action}"))?;
+
+    if let Some(errors) = graphql_errors(&response) {
+        anyhow::bail!("couldn't {action}: {errors}");
+    }
+
+    Ok(())
+}
+
+/// GitHub reports the failure of a GraphQL mutation in the response's body, alongside a successful
+/// status code; returns the messages of the errors, if any.
+fn graphql_errors(response: &serde_json::Value) -> Option<String> {
+    let errors = response.get("errors")?.as_array()?;
+    if errors.is_empty() {
+        return None;
+    }
+
+    Some(
+        errors
+            .iter()
+            .map(|e| {
+                e.get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("unknown error")
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+    )
+}
+
+/// Renders the title and the message of the merge commit, for the templates that are configured.
+fn render_commit_text(
+    settings: &RepoSettings,
+    pr: &PullRequest,
+    pr_check: &PRCheck<PRCheckInProgress>,
+) -> anyhow::Result<(Option<String>, Option<String>)> {
+    if settings.commit_title_template.is_none() && settings.commit_message_template.is_none() {
+        return Ok((None, None));
+    }
+
+    let context = CommitContext {
+        number: pr.number,
+        title: pr.title.clone().unwrap_or_default(),
+        body: pr.body.clone().unwrap_or_default(),
+        author: pr
+            .user
+            .as_ref()
+            .map(|user| user.login.clone())
+            .unwrap_or_default(),
+        head_ref: pr.head.ref_field.clone(),
+        ecosystem: pr_check.ecosystem.clone(),
+        upgrade: pr_check.upgrade.as_ref().map(CommitUpgrade::from),
+    };
+
+    let title = settings
+        .commit_title_template
+        .as_ref()
+        .map(|t| t.render(&context))
+        .transpose()
+        .context("couldn't render commit title")?;
+    let message = settings
+        .commit_message_template
+        .as_ref()
+        .map(|t| t.render(&context))
+        .transpose()
+        .context("couldn't render commit message")?;
+
+    Ok((title, message))
+}
+
+/// Lists the reviews of a PR as (reviewer, state) pairs, in chronological order; fails if they
+/// don't fit in the maximum number of pages, as the latest ones would be left out.
+async fn list_reviews(
+    ctx: &RepoContext<'_>,
+    number: u64,
+) -> anyhow::Result<Vec<(String, ReviewState, Option<String>)>> {
+    let mut page = ctx
+        .client
+        .pulls(ctx.owner, ctx.repo)
+        .list_reviews(number)
+        .per_page(PER_PAGE)
+        .send()
+        .await
+        .context("couldn't get pr reviews")?;
+    let mut reviews = std::mem::take(&mut page.items);
+
+    for _ in 1..ctx.max_pages {
+        let Some(mut next_page) = ctx
+            .client
+            .get_page::<Review>(&page.next)
+            .await
+            .context("couldn't get next page of pr reviews")?
+        else {
+            break;
+        };
+
+        reviews.append(&mut next_page.items);
+        page = next_page;
+    }
+
+    if page.next.is_some() {
+        anyhow::bail!(
+            "couldn't fetch all reviews within {} page(s); consider raising max_pages",
+            ctx.max_pages
+        );
+    }
+
+    Ok(reviews
+        .into_iter()
+        .filter_map(|review| Some((review.user?.login, review.state?, review.commit_id)))
+        .collect())
+}
+
+/// Returns when the PR was created, or when its head was last pushed to, whichever is later.
+async fn last_pushed_at(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    pull_request: &PullRequest,
+) -> anyhow::Result<DateTime<Utc>> {
+    let head = client
+        .commits(owner, repo)
+        .get(&pull_request.head.sha)
+        .await
+        .context("couldn't get head commit")?;
+
+    let committed_at = head.commit.committer.and_then(|c| c.date);
+
+    // if GitHub doesn't say when the PR was created, it's safest to assume it's brand new
+    Ok(pull_request
+        .created_at
+        .max(committed_at)
+        .unwrap_or_else(Utc::now))
+}
+
+async fn update_branch(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    number: u64,
+) -> anyhow::Result<()> {
+    let accepted = client
+        .pulls(owner, repo)
+        .update_branch(number)
+        .await
+        .context("couldn't update PR's branch")?;
+
+    if !accepted {
+        anyhow::bail!("GitHub didn't accept the request to update PR's branch");
+    }
+
+    Ok(())
+}
+
+/// Asks the bot that created the PR to rebase it, unless a rebase asked for earlier is still
+/// pending; returns `None` if the PR wasn't created by a bot that can be asked to rebase it.
+async fn request_rebase(ctx: &RepoContext<'_>, pr: &PullRequest) -> anyhow::Result<Option<DQ>> {
+    let author = pr.user.as_ref().map(|user| user.login.as_str());
+    let Some(bot) = detect_bot(author, &pr.head.ref_field) else {
+        return Ok(None);
+    };
+
+    match bot {
+        UpdateBot::Dependabot => {
+            let comments = list_comments(ctx, pr.number).await?;
+            if let Some(requested_at) = latest_rebase_request(&comments, ctx.login) {
+                // Dependabot force pushes the rebased commits, which moves the head commit's date
+                let pushed_at = last_pushed_at(ctx.client, ctx.owner, ctx.repo, pr).await?;
+                if rebase_pending(requested_at, pushed_at, Utc::now()) {
+                    return Ok(Some(DQ::RebasePending {
+                        bot,
+                        requested_at: Some(requested_at),
+                    }));
+                }
+            }
+
+            if ctx.execute {
+                ctx.client
+                    .issues(ctx.owner, ctx.repo)
+                    .create_comment(pr.number, DEPENDABOT_REBASE_COMMENT)
+                    .await
+                    .context("couldn't ask dependabot to rebase PR")?;
+            }
+        }
+        UpdateBot::Renovate => {
+            let body = pr.body.as_deref().unwrap_or_default();
+            // Renovate unticks the checkbox once it has rebased the PR
+            if body.contains(RENOVATE_REBASE_CHECKBOX_TICKED) {
+                return Ok(Some(DQ::RebasePending {
+                    bot,
+                    requested_at: None,
+                }));
+            }
+
+            let Some(body) = tick_rebase_checkbox(body) else {
+                return Ok(None);
+            };
+
+            if ctx.execute {
+                ctx.client
+                    .pulls(ctx.owner, ctx.repo)
+                    .update(pr.number)
+                    .body(body)
+                    .send()
+                    .await
+                    .context("couldn't ask renovate to rebase PR")?;
+            }
+        }
+    }
+
+    Ok(Some(DQ::RebaseRequested(bot)))
+}
+
+/// Lists the comments on a PR as (author, body, created at) triples, in chronological order; like
+/// `list_reviews`, fails if they don't fit in the maximum number of pages.
+async fn list_comments(
+    ctx: &RepoContext<'_>,
+    number: u64,
+) -> anyhow::Result<Vec<(String, String, DateTime<Utc>)>> {
+    let mut page = ctx
+        .client
+        .issues(ctx.owner, ctx.repo)
+        .list_comments(number)
+        .per_page(PER_PAGE)
+        .send()
+        .await
+        .context("couldn't get pr comments")?;
+    let mut comments = std::mem::take(&mut page.items);
+
+    for _ in 1..ctx.max_pages {
+        let Some(mut next_page) = ctx
+            .client
+            .get_page::<Comment>(&page.next)
+            .await
+            .context("couldn't get next page of pr comments")?
+        else {
+            break;
+        };
+
+        comments.append(&mut next_page.items);
+        page = next_page;
+    }
+
+    if page.next.is_some() {
+        anyhow::bail!(
+            "couldn't fetch all comments within {} page(s); consider raising max_pages",
+            ctx.max_pages
+        );
+    }
+
+    Ok(comments
+        .into_iter()
+        .filter_map(|comment| Some((comment.user.login, comment.body?, comment.created_at)))
+        .collect())
+}
+
+/// Returns when mrj (commenting as `login`) last asked Dependabot to rebase the PR; if `login`
+/// isn't known, anyone's request counts.
+fn latest_rebase_request(
+    comments: &[(String, String, DateTime<Utc>)],
+    login: Option<&str>,
+) -> Option<DateTime<Utc>> {
+    comments
+        .iter()
+        .filter(|(author, body, _)| {
+            login.is_none_or(|login| author == login) && body.trim() == DEPENDABOT_REBASE_COMMENT
+        })
+        .map(|(_, _, created_at)| *created_at)
+        .max()
+}
+
+/// Whether a rebase asked for at `requested_at` is yet to happen, and still worth waiting for; a
+/// request the bot hasn't acted on within `REBASE_REQUEST_EXPIRY` gets made again.
+fn rebase_pending(
+    requested_at: DateTime<Utc>,
+    pushed_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> bool {
+    requested_at > pushed_at && now - requested_at < REBASE_REQUEST_EXPIRY
+}
+
+/// Ticks the rebase checkbox in the description of a Renovate PR; returns `None` if there's no
+/// unticked checkbox to tick.
+fn tick_rebase_checkbox(body: &str) -> Option<String> {
+    body.contains(RENOVATE_REBASE_CHECKBOX)
+        .then(|| body.replacen(RENOVATE_REBASE_CHECKBOX, RENOVATE_REBASE_CHECKBOX_TICKED, 1))
+}
+
+async fn approve_pr(
+    client: &Octocrab,
+    owner: &str,
+    repo: &str,
+    number: u64,
+    sha: &str,
+) -> anyhow::Result<()> {
+    // octocrab only offers creating reviews via a deprecated builder
+    let _: Review = client
+        .post(
+            format!("/repos/{owner}/{repo}/pulls/{number}/reviews"),
+            Some(&serde_json::json!({
+                "commit_id": sha,
+                "body": "Approved by mrj",
+                "event": "APPROVE",
+            })),
+        )
+        .await
+        .context("couldn't approve PR")?;
+
+    Ok(())
+}
+
+/// Returns the first trusted reviewer whose latest say on the PR is an approval of its head
+/// commit (`head_sha`); approvals of earlier commits don't vouch for what was pushed since.
+/// Nobody is trusted if there's no list of trusted reviewers.
+fn approved_by(
+    settings: &RepoSettings,
+    reviews: &[(String, ReviewState, Option<String>)],
+    head_sha: &str,
+) -> Option<String> {
+    latest_reviews(reviews)
+        .into_iter()
+        .find(|(reviewer, state, commit_id)| {
+            *state == ReviewState::Approved
+                && *commit_id == Some(head_sha)
+                && settings.trusted_reviewers.iter().any(|r| r == reviewer)
+        })
+        .map(|(reviewer, _, _)| reviewer.to_string())
+}
+
+/// Whether the latest review by `login` approves `head_sha`; used to recognise approvals mrj
+/// submitted on earlier runs.
+fn has_approved(
+    reviews: &[(String, ReviewState, Option<String>)],
+    login: &str,
+    head_sha: &str,
+) -> bool {
+    latest_reviews(reviews)
+        .into_iter()
+        .any(|(reviewer, state, commit_id)| {
+            reviewer == login && state == ReviewState::Approved && commit_id == Some(head_sha)
+        })
+}
+
+/// Returns each reviewer's latest review that isn't a comment.
+fn latest_reviews(
+    reviews: &[(String, ReviewState, Option<String>)],
+) -> Vec<(&str, ReviewState, Option<&str>)> {
+    let mut latest: Vec<(&str, ReviewState, Option<&str>)> = vec![];
+
+    for (reviewer, state, commit_id) in reviews {
+        // comments don't change whether a reviewer has approved a PR or not
+        if matches!(state, ReviewState::Commented | ReviewState::Pending) {
+            continue;
+        }
+
+        match latest.iter_mut().find(|(r, _, _)| r == reviewer) {
+            Some((_, s, c)) => (*s, *c) = (*state, commit_id.as_deref()),
+            None => latest.push((reviewer, *state, commit_id.as_deref())),
+        }
+    }
+
+    latest
+}
+
+fn pr_labels(pull_request: &PullRequest) -> impl Iterator<Item = &str> {
+    pull_request
+        .labels
+        .iter()
+        .flatten()
+        .map(|label| label.name.as_str())
+}
+
+/// Checks a PR's labels against the blocking and required ones, and whether merges for the repo
+/// have been frozen (via a PR with the freeze label). Labels are compared case-insensitively, the
+/// same as GitHub does.
+fn evaluate_labels(
+    settings: &RepoSettings,
+    labels: &[&str],
+    frozen_by: Option<&(String, u64)>,
+) -> Result<Vec<Q>, DQ> {
+    let has_label = |wanted: &str| labels.iter().any(|l| l.eq_ignore_ascii_case(wanted));
+
+    if let Some(blocking) = settings.blocking_labels.iter().find(|l| has_label(l)) {
+        return Err(DQ::BlockingLabel(blocking.clone()));
+    }
+
+    if let Some((label, pr)) = frozen_by {
+        return Err(DQ::Frozen {
+            label: label.clone(),
+            pr: *pr,
+        });
+    }
+
+    let mut qualifications = vec![];
+    for required in &settings.required_labels {
+        if !has_label(required) {
+            return Err(DQ::RequiredLabelMissing(required.clone()));
+        }
+        qualifications.push(Q::Label(required.clone()));
+    }
+
+    Ok(qualifications)
+}
+
+/// Returns when a PR's cool-down starts: when it was last pushed to, or when the release it
+/// upgrades to came out, whichever is later. The release date comes from upstream release notes,
+/// which the package's author controls, so it can only ever make the wait longer.
+fn cool_down_start(pushed_at: DateTime<Utc>, released_at: Option<DateTime<Utc>>) -> DateTime<Utc> {
+    released_at.map_or(pushed_at, |released_at| released_at.max(pushed_at))
+}
+
+/// Checks whether at least `min_age` has passed since `aged_since`; if not, the disqualification
+/// says when it will have.
+fn evaluate_pr_age(
+    min_age: &HumanDuration,
+    aged_since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), DQ> {
+    let eligible_at = TimeDelta::from_std(min_age.duration)
+        .ok()
+        .and_then(|age| aged_since.checked_add_signed(age))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+    match (eligible_at - now).to_std() {
+        Ok(remaining) if !remaining.is_zero() => Err(DQ::CoolingDown {
+            eligible_at,
+            remaining: HumanDuration::from(remaining),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Checks whether the PR has been open for longer than `max_age`.
+fn evaluate_staleness(
+    max_age: &HumanDuration,
+    created_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), DQ> {
+    let stale = TimeDelta::from_std(max_age.duration)
+        .ok()
+        .and_then(|age| created_at.checked_add_signed(age))
+        .is_some_and(|stale_at| stale_at <= now);
+
+    if stale {
+        return Err(DQ::Stale {
+            max_age: max_age.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks the number of files the PR changes, and the number of lines it adds, against the
+/// configured maximums; counts GitHub doesn't report are let through.
+fn evaluate_diff_size(
+    settings: &RepoSettings,
+    changed_files: Option<u64>,
+    additions: Option<u64>,
+) -> Result<(), DQ> {
+    if let (Some(max), Some(found)) = (settings.max_changed_files, changed_files)
+        && found > max
+    {
+        return Err(DQ::TooManyChangedFiles { found, max });
+    }
+
+    if let (Some(max), Some(found)) = (settings.max_additions, additions)
+        && found > max
+    {
+        return Err(DQ::TooManyAdditions { found, max });
+    }
+
+    Ok(())
+}
+
+/// Checks that every path the PR touches matches one of the allowed paths.
+fn evaluate_paths(settings: &RepoSettings, paths: &[String]) -> Result<Q, DQ> {
+    let not_allowed = paths
+        .iter()
+        .filter(|path| !settings.allowed_paths.iter().any(|g| g.is_match(path)))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if !not_allowed.is_empty() {
+        return Err(DQ::PathsNotAllowed(not_allowed));
+    }
+
+    Ok(Q::AllowedPaths(paths.len()))
+}
+
+/// Checks the package being upgraded against the allow and deny lists; returns `None` if none
+/// are configured.
+fn evaluate_package(settings: &RepoSettings, upgrade: Option<&Upgrade>) -> Option<Result<Q, DQ>> {
+    if settings.allow_packages.is_empty()
+        && settings.deny_packages.is_empty()
+        && settings.deny_versions.is_empty()
+    {
+        return None;
+    }
+
+    let Some(upgrade) = upgrade else {
+        return Some(Err(DQ::PackageNotAllowed(None)));
+    };
+    let package = &upgrade.package;
+
+    if settings.deny_packages.iter().any(|g| g.is_match(package)) {
+        return Some(Err(DQ::PackageDenied(package.clone())));
+    }
+
+    if settings
+        .deny_versions
+        .iter()
+        .any(|pv| pv.is_match(package, &upgrade.to))
+    {
+        return Some(Err(DQ::VersionDenied {
+            package: package.clone(),
+            version: upgrade.to.to_string(),
+        }));
+    }
+
+    if !settings.allow_packages.is_empty()
+        && !settings.allow_packages.iter().any(|g| g.is_match(package))
+    {
+        return Some(Err(DQ::PackageNotAllowed(Some(package.clone()))));
+    }
+
+    Some(Ok(Q::Package(package.clone())))
+}
+
+/// Lists the checks for a commit, ie. its check runs and its commit statuses (which is how
+/// external CI systems like Jenkins report); if configured to, keeps polling them until the ones
+/// that aren't ignored have concluded, or the timeout is up. Also returns whether any polling
+/// happened.
+async fn list_checks(
+    ctx: &RepoContext<'_>,
+    sha: &str,
+    settings: &RepoSettings,
+) -> anyhow::Result<(Vec<Check>, bool)> {
+    // GitHub waits for pending checks itself when merging via auto-merge or a merge queue
+    let wait = settings
+        .wait_for_pending_checks
+        .as_ref()
+        .filter(|_| settings.merge_strategy.is_direct());
+    let deadline = wait.map(|w| Instant::now() + w.timeout.duration);
+    let mut waited = false;
+
+    loop {
+        let check_runs = list_check_runs(ctx, sha).await?;
+        let statuses = list_statuses(ctx, sha).await?;
+
+        let checks = check_runs
+            .iter()
+            .map(Check::from_check_run)
+            .chain(statuses.iter().filter_map(Check::from_status))
+            .collect::<Vec<_>>();
+
+        let pending = checks
+            .iter()
+            .any(|c| c.conclusion.is_none() && !is_check_ignored(settings, &c.name));
+
+        let remaining = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+        match (wait, remaining) {
+            (Some(w), Some(remaining)) if pending && !remaining.is_zero() => {
+                tokio::time::sleep(w.poll_interval.duration.min(remaining)).await;
+                waited = true;
+            }
+            _ => return Ok((checks, waited)),
+        }
+    }
+}
+
+/// Lists all check runs for a commit; fails if they don't fit in the maximum number of pages, as
+/// deciding based on some of them could lead to a failing check being missed.
+async fn list_check_runs(ctx: &RepoContext<'_>, sha: &str) -> anyhow::Result<Vec<CheckRun>> {
+    let mut check_runs = vec![];
+
+    for page in 1..=ctx.max_pages {
+        let response = ctx
+            .client
+            .checks(ctx.owner, ctx.repo)
+            .list_check_runs_for_git_ref(Commitish::from(sha.to_string()))
+            .per_page(PER_PAGE)
+            .page(page)
+            .send()
+            .await
+            .context("couldn't get pr checks")?;
+
+        let num_fetched = response.check_runs.len();
+        check_runs.extend(response.check_runs);
+
+        if is_last_page(num_fetched, check_runs.len(), response.total_count) {
+            return Ok(check_runs);
+        }
+    }
+
+    anyhow::bail!(
+        "couldn't fetch all check runs within {} page(s); consider raising max_pages",
+        ctx.max_pages
+    )
+}
+
+/// Like `list_check_runs`, but for the latest commit status of each context.
+async fn list_statuses(ctx: &RepoContext<'_>, sha: &str) -> anyhow::Result<Vec<Status>> {
+    let mut statuses = vec![];
+
+    for page in 1..=ctx.max_pages {
+        // octocrab's combined_status_for_ref only accepts branches and tags
+        let response: CombinedStatus = ctx
+            .client
+            .get(
+                format!("/repos/{}/{}/commits/{sha}/status", ctx.owner, ctx.repo),
+                Some(&[("per_page", u32::from(PER_PAGE)), ("page", page)]),
+            )
+            .await
+            .context("couldn't get pr commit statuses")?;
+
+        let num_fetched = response.statuses.len();
+        statuses.extend(response.statuses);
+
+        let total_count = u64::try_from(response.total_count).unwrap_or_default();
+        if is_last_page(num_fetched, statuses.len(), total_count) {
+            return Ok(statuses);
+        }
+    }
+
+    anyhow::bail!(
+        "couldn't fetch all commit statuses within {} page(s); consider raising max_pages",
+        ctx.max_pages
+    )
+}
+
+/// Lists the paths the PR touches; for renamed files, both the old and the new path are included.
+async fn list_changed_paths(
+    ctx: &RepoContext<'_>,
+    number: u64,
+    changed_files: Option<u64>,
+) -> anyhow::Result<Vec<String>> {
+    let mut paths = vec![];
+    let mut num_files = 0;
+
+    for page in 1..=ctx.max_pages {
+        let files: Vec<DiffEntry> = ctx
+            .client
+            .get(
+                format!("/repos/{}/{}/pulls/{number}/files", ctx.owner, ctx.repo),
+                Some(&[("per_page", u32::from(PER_PAGE)), ("page", page)]),
+            )
+            .await
+            .context("couldn't get pr files")?;
+
+        let num_fetched = files.len();
+        num_files += num_fetched;
+        for file in files {
+            paths.extend(file.previous_filename);
+            paths.push(file.filename);
+        }
+
+        if is_last_page(num_fetched, num_files, changed_files.unwrap_or(u64::MAX)) {
+            return Ok(paths);
+        }
+    }
+
+    anyhow::bail!(
+        "couldn't fetch all changed files within {} page(s); consider raising max_pages",
+        ctx.max_pages
+    )
+}
+
+fn is_last_page(num_fetched: usize, num_fetched_so_far: usize, total_count: u64) -> bool {
+    num_fetched < usize::from(PER_PAGE) || num_fetched_so_far as u64 >= total_count
+}
+
+fn is_check_ignored(settings: &RepoSettings, name: &str) -> bool {
+    settings
+        .ignored_checks
+        .iter()
+        .any(|pattern| pattern.is_match(name))
+}
+
+/// Checks the conclusions of a PR's checks; skipped checks, and neutral ones, are only accepted
+/// if configured to be. Pending checks are accepted when GitHub does the merging (as it waits for
+/// them).
+fn evaluate_checks(settings: &RepoSettings, checks: &[&Check]) -> Result<Vec<Q>, DQ> {
+    let mut qualifications = vec![];
+
+    for check in checks {
+        if check.conclusion.is_none() && !settings.merge_strategy.is_direct() {
+            qualifications.push(Q::PendingCheck(check.name.clone()));
+            continue;
+        }
+
+        let conclusion = match check.conclusion.as_deref() {
+            Some("success") => "success",
+            Some("skipped") if settings.merge_if_checks_skipped => "success",
+            Some("neutral") if settings.merge_if_checks_neutral => "neutral",
+            other => {
+                return Err(DQ::Check {
+                    name: check.name.clone(),
+                    conclusion: other.map(str::to_string),
+                });
+            }
+        };
+
+        qualifications.push(Q::Check {
+            name: check.name.clone(),
+            conclusion: conclusion.to_string(),
+        });
+    }
+
+    Ok(qualifications)
+}
+
+/// Gathers what rules can refer to; `pull_request` is the PR as listed, and `pr` its details.
+fn rule_attributes(
+    pull_request: &PullRequest,
+    pr: &PullRequest,
+    pr_check: &PRCheck<PRCheckInProgress>,
+    files: Vec<String>,
+    check_runs: &[&Check],
+    now: DateTime<Utc>,
+) -> PrAttributes {
+    PrAttributes {
+        author: pull_request.user.as_ref().map(|u| u.login.clone()),
+        title: pull_request.title.clone().unwrap_or_default(),
+        head: pull_request.head.ref_field.clone(),
+        base: pull_request.base.ref_field.clone(),
+        labels: pr_labels(pull_request).map(str::to_string).collect(),
+        ecosystem: pr_check.ecosystem.clone(),
+        package: pr_check.upgrade.as_ref().map(|u| u.package.clone()),
+        update_type: pr_check.upgrade.as_ref().and_then(|u| u.update_type()),
+        age: pull_request
+            .created_at
+            .and_then(|created_at| (now - created_at).to_std().ok()),
+        changed_files: pr.changed_files,
+        additions: pr.additions,
+        files,
+        checks: check_runs
+            .iter()
+            .filter(|c| c.conclusion.as_deref() == Some("success"))
+            .map(|c| c.name.clone())
+            .collect(),
+    }
+}
+
+/// Evaluates rules in order, and goes with the first one that matches. If none does, the PR is
+/// only disqualified if there are allow rules; deny rules on their own act as a blocklist.
+fn evaluate_rules(rules: &[Rule], attributes: &PrAttributes) -> Option<Result<Q, DQ>> {
+    if rules.is_empty() {
+        return None;
+    }
+
+    match rules.iter().find(|r| r.rule.is_match(attributes)) {
+        Some(r) if r.rule.action == RuleAction::Allow => Some(Ok(Q::Rule(r.name.clone()))),
+        Some(r) => Some(Err(DQ::Rule(r.name.clone()))),
+        None if rules.iter().any(|r| r.rule.action == RuleAction::Allow) => {
+            Some(Err(DQ::NoRuleMatched))
+        }
+        None => None,
+    }
+}
+
+/// Makes sure that the required checks have been registered for a PR, and that there are enough
+/// of them; a PR with no checks at all would otherwise sail through. Ignored checks don't count.
+fn evaluate_check_presence(settings: &RepoSettings, check_names: &[&str]) -> Result<(), DQ> {
+    if let Some(missing) = settings
+        .required_checks
+        .iter()
+        .find(|c| !check_names.iter().any(|name| c.is_match(name)))
+    {
+        return Err(DQ::RequiredCheckMissing(missing.to_string()));
+    }
+
+    if check_names.len() < settings.min_checks {
+        return Err(DQ::TooFewChecks {
+            found: check_names.len(),
+            required: settings.min_checks,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Repo;
+    use crate::merge::tests::config;
+    use crate::upgrade::UpdateType;
+    use insta::assert_yaml_snapshot;
+
+    const PR_SHA: &str = "9f2c1e4b7a3d8c6e5f0a1b2c3d4e5f6a7b8c9d0e";
+
+    #[test]
+    fn evaluating_package_is_skipped_when_no_lists_are_configured() {
+        // GIVEN
+        let settings = settings("");
+        let upgrade = upgrade("bump clap from 4.5.39 to 4.5.40");
+
+        // WHEN
+        let result = evaluate_package(&settings, upgrade.as_ref());
+
+        // THEN
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn evaluating_package_qualifies_allowed_package() {
+        // GIVEN
+        let settings = settings(r#"allow_packages = ["clap*", "serde"]"#);
+        let upgrade = upgrade("bump clap_complete from 4.5.39 to 4.5.40");
+
+        // WHEN
+        let result = evaluate_package(&settings, upgrade.as_ref());
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Ok:
+          Package: clap_complete
+        ");
+    }
+
+    #[test]
+    fn evaluating_package_disqualifies_package_not_in_allow_list() {
+        // GIVEN
+        let settings = settings(r#"allow_packages = ["serde"]"#);
+        let upgrade = upgrade("bump clap from 4.5.39 to 4.5.40");
+
+        // WHEN
+        let result = evaluate_package(&settings, upgrade.as_ref());
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Err:
+          PackageNotAllowed: clap
+        ");
+    }
+
+    #[test]
+    fn evaluating_package_disqualifies_unknown_package() {
+        // GIVEN
+        let settings = settings(r#"deny_packages = ["openssl"]"#);
+
+        // WHEN
+        let result = evaluate_package(&settings, None);
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Err:
+          PackageNotAllowed: ~
+        ");
+    }
+
+    #[test]
+    fn evaluating_package_prefers_deny_list_over_allow_list() {
+        // GIVEN
+        let settings = settings(
+            r#"
+allow_packages = ["**"]
+deny_packages = ["openssl*"]
+"#,
+        );
+        let upgrade = upgrade("bump openssl-sys from 0.9.108 to 0.9.109");
+
+        // WHEN
+        let result = evaluate_package(&settings, upgrade.as_ref());
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Err:
+          PackageDenied: openssl-sys
+        ");
+    }
+
+    #[test]
+    fn evaluating_package_disqualifies_denied_version() {
+        // GIVEN
+        let settings = settings(r#"deny_versions = ["serde@1.0.999", "@types/node@20.2.0"]"#);
+
+        // WHEN
+        let results = [
+            "bump serde from 1.0.228 to 1.0.999",
+            "bump serde from 1.0.228 to 1.0.229",
+            "bump @types/node from 20.1.0 to 20.2.0",
+        ]
+        .map(|title| evaluate_package(&settings, upgrade(title).as_ref()));
+
+        // THEN
+        assert_yaml_snapshot!(results, @r#"
+        - Err:
+            VersionDenied:
+              package: serde
+              version: 1.0.999
+        - Ok:
+            Package: serde
+        - Err:
+            VersionDenied:
+              package: "@types/node"
+              version: 20.2.0
+        "#);
+    }
+
+    #[test]
+    fn evaluating_check_presence_passes_when_nothing_is_required() {
+        // GIVEN
+        let settings = settings("");
+
+        // WHEN
+        let result = evaluate_check_presence(&settings, &[]);
+
+        // THEN
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn evaluating_check_presence_matches_exact_names_globs_and_regexes() {
+        // GIVEN
+        let settings = settings(r#"required_checks = ["test", "re:lint (.+)", "build (ubuntu)"]"#);
+
+        // WHEN
+        let result = evaluate_check_presence(
+            &settings,
+            &["build (ubuntu)", "lint (clippy)", "test", "audit"],
+        );
+
+        // THEN
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn evaluating_check_presence_disqualifies_missing_required_check() {
+        // GIVEN
+        let settings = settings(r#"required_checks = ["test", "lint"]"#);
+
+        // WHEN
+        let results = [
+            evaluate_check_presence(&settings, &["test"]),
+            evaluate_check_presence(&settings, &[]),
+            evaluate_check_presence(&settings, &["tests", "lint"]),
+        ];
+
+        // THEN
+        assert_yaml_snapshot!(results, @"
+        - Err:
+            RequiredCheckMissing: lint
+        - Err:
+            RequiredCheckMissing: test
+        - Err:
+            RequiredCheckMissing: test
+        ");
+    }
+
+    #[test]
+    fn evaluating_check_presence_disqualifies_too_few_checks() {
+        // GIVEN
+        let settings = settings("min_checks = 2");
+
+        // WHEN
+        let results = [
+            evaluate_check_presence(&settings, &["test"]),
+            evaluate_check_presence(&settings, &["test", "lint"]),
+        ];
+
+        // THEN
+        assert_yaml_snapshot!(results, @"
+        - Err:
+            TooFewChecks:
+              found: 1
+              required: 2
+        - Ok: ~
+        ");
+    }
+
+    #[test]
+    fn evaluating_checks_respects_skipped_and_neutral_settings() {
+        // GIVEN
+        let settings = settings(
+            r#"
+merge_if_checks_skipped = true
+merge_if_checks_neutral = true
+"#,
+        );
+        let checks = [
+            check("test", Some("success")),
+            check("ci/jenkins", Some("success")),
+            check("lint", Some("skipped")),
+            check("coverage", Some("neutral")),
+        ];
+
+        // WHEN
+        let result = evaluate_checks(&settings, &checks.iter().collect::<Vec<_>>());
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Ok:
+          - Check:
+              name: test
+              conclusion: success
+          - Check:
+              name: ci/jenkins
+              conclusion: success
+          - Check:
+              name: lint
+              conclusion: success
+          - Check:
+              name: coverage
+              conclusion: neutral
+        ");
+    }
+
+    #[test]
+    fn evaluating_checks_disqualifies_failed_and_pending_checks() {
+        // GIVEN
+        let settings = settings("merge_if_checks_neutral = false");
+        let check_sets = [
+            [
+                check("test", Some("success")),
+                check("ci/jenkins", Some("error")),
+            ],
+            [check("test", Some("success")), check("buildkite", None)],
+            [
+                check("test", Some("neutral")),
+                check("lint", Some("success")),
+            ],
+        ];
+
+        // WHEN
+        let results =
+            check_sets.map(|checks| evaluate_checks(&settings, &checks.iter().collect::<Vec<_>>()));
+
+        // THEN
+        assert_yaml_snapshot!(results, @"
+        - Err:
+            Check:
+              name: ci/jenkins
+              conclusion: error
+        - Err:
+            Check:
+              name: buildkite
+              conclusion: ~
+        - Err:
+            Check:
+              name: test
+              conclusion: neutral
+        ");
+    }
+
+    #[test]
+    fn evaluating_checks_accepts_pending_checks_when_github_merges() {
+        // GIVEN
+        let settings = settings(r#"merge_strategy = "auto-merge""#);
+        let checks = [
+            check("test", Some("success")),
+            check("buildkite", None),
+            check("ci/jenkins", Some("failure")),
+        ];
+
+        // WHEN
+        let results = [
+            evaluate_checks(&settings, &checks[..2].iter().collect::<Vec<_>>()),
+            evaluate_checks(&settings, &checks.iter().collect::<Vec<_>>()),
+        ];
+
+        // THEN
+        assert_yaml_snapshot!(results, @"
+        - Ok:
+            - Check:
+                name: test
+                conclusion: success
+            - PendingCheck: buildkite
+        - Err:
+            Check:
+              name: ci/jenkins
+              conclusion: failure
+        ");
+    }
+
+    #[test]
+    fn superseding_requires_same_package_and_an_older_pr() {
+        // GIVEN
+        let settings = settings("");
+        let older = at(2025, 6, 1, 0, 0);
+        let newer = at(2025, 6, 2, 0, 0);
+        let merged_upgrade = upgrade_from_head(
+            "build(deps): bump clap from 4.5.39 to 4.5.40",
+            "dependabot/cargo/clap-4.5.40",
+        );
+        let merged = upgrade_pr(&merged_upgrade, "dependabot/cargo/clap-4.5.40", Some(newer));
+        let cases = [
+            (
+                "older PR for the same package",
+                "build(deps): bump clap from 4.5.38 to 4.5.39",
+                "dependabot/cargo/clap-4.5.39",
+                "main",
+                "dependabot[bot]",
+                Some(older),
+            ),
+            (
+                "newer PR for the same package",
+                "build(deps): bump clap from 4.5.40 to 4.5.41",
+                "dependabot/cargo/clap-4.5.41",
+                "main",
+                "dependabot[bot]",
+                Some(at(2025, 6, 3, 0, 0)),
+            ),
+            (
+                "older PR for another package",
+                "build(deps): bump serde from 1.0.218 to 1.0.219",
+                "dependabot/cargo/serde-1.0.219",
+                "main",
+                "dependabot[bot]",
+                Some(older),
+            ),
+            (
+                "older PR for the same package in another ecosystem",
+                "build(deps): bump clap from 4.5.38 to 4.5.39",
+                "dependabot/npm_and_yarn/clap-4.5.39",
+                "main",
+                "dependabot[bot]",
+                Some(older),
+            ),
+            (
+                "PR without a creation date",
+                "build(deps): bump clap from 4.5.38 to 4.5.39",
+                "dependabot/cargo/clap-4.5.39",
+                "main",
+                "dependabot[bot]",
+                None,
+            ),
+            (
+                "older PR against another base",
+                "build(deps): bump clap from 4.5.38 to 4.5.39",
+                "dependabot/cargo/clap-4.5.39",
+                "release/1.x",
+                "dependabot[bot]",
+                Some(older),
+            ),
+            (
+                "older PR for the same package in another directory",
+                "build(deps): bump clap from 4.5.38 to 4.5.39 in /tools",
+                "dependabot/cargo/tools/clap-4.5.39",
+                "main",
+                "dependabot[bot]",
+                Some(older),
+            ),
+            (
+                "older PR upgrading to a higher version",
+                "build(deps): bump clap from 4.5.39 to 5.0.0",
+                "dependabot/cargo/clap-5.0.0",
+                "main",
+                "dependabot[bot]",
+                Some(older),
+            ),
+            (
+                "older PR for the same package by an untrusted author",
+                "build(deps): bump clap from 4.5.38 to 4.5.39",
+                "dependabot/cargo/clap-4.5.39",
+                "main",
+                "someone",
+                Some(older),
+            ),
+        ];
+
+        // WHEN
+        let results = cases
+            .iter()
+            .map(|(case, title, head, base, author, created_at)| {
+                let other_upgrade = upgrade_from_head(title, head);
+                let other = UpgradePr {
+                    base,
+                    author: Some(author),
+                    ..upgrade_pr(&other_upgrade, head, *created_at)
+                };
+                (*case, supersedes(&settings, &merged, &other))
+            })
+            .collect::<Vec<_>>();
+
+        // THEN
+        assert_yaml_snapshot!(results, @"
+        - - older PR for the same package
+          - true
+        - - newer PR for the same package
+          - false
+        - - older PR for another package
+          - false
+        - - older PR for the same package in another ecosystem
+          - false
+        - - PR without a creation date
+          - false
+        - - older PR against another base
+          - false
+        - - older PR for the same package in another directory
+          - false
+        - - older PR upgrading to a higher version
+          - false
+        - - older PR for the same package by an untrusted author
+          - false
+        ");
+    }
+
+    #[test]
+    fn only_open_search_hits_by_authors_that_can_be_trusted_are_fetched() {
+        // GIVEN
+        let config = config(
+            r#"
+[ecosystem."npm_and_yarn"]
+trusted_authors = ["renovate[bot]"]
+"#,
+        );
+        let settings = config.settings_for(&Repo {
+            owner: "dhth".to_string(),
+            repo: "mrj".to_string(),
+        });
+        let hits = [
+            ("open PR by a trusted author", true, "dependabot[bot]"),
+            ("closed PR by a trusted author", false, "dependabot[bot]"),
+            ("open PR by an untrusted author", true, "someone"),
+            (
+                "open PR by an author trusted for an ecosystem",
+                true,
+                "renovate[bot]",
+            ),
+        ];
+
+        // WHEN
+        let results = hits.map(|(case, open, author)| {
+            let hit = SearchHit {
+                repo: Repo {
+                    owner: "dhth".to_string(),
+                    repo: "mrj".to_string(),
+                },
+                number: 1,
+                open,
+                author: author.to_string(),
+            };
+            (case, could_qualify(&config, &settings, &hit))
+        });
+
+        // THEN
+        assert_yaml_snapshot!(results, @"
+        - - open PR by a trusted author
+          - true
+        - - closed PR by a trusted author
+          - false
+        - - open PR by an untrusted author
+          - false
+        - - open PR by an author trusted for an ecosystem
+          - true
+        ");
+    }
+
+    #[test]
+    fn finding_graphql_errors_works() {
+        // GIVEN
+        let responses = [
+            serde_json::json!({
+                "data": {
+                    "enablePullRequestAutoMerge": { "clientMutationId": null }
+                }
+            }),
+            serde_json::json!({
+                "data": { "enablePullRequestAutoMerge": null },
+                "errors": [
+                    { "type": "UNPROCESSABLE", "message": "Pull request Auto merge is not allowed for this repository" },
+                    { "message": "Head sha didn't match expected head sha" }
+                ]
+            }),
+            serde_json::json!({ "errors": [] }),
+        ];
+
+        // WHEN
+        let errors = responses.map(|r| graphql_errors(&r));
+
+        // THEN
+        assert_yaml_snapshot!(errors, @r#"
+        - ~
+        - "Pull request Auto merge is not allowed for this repository; Head sha didn't match expected head sha"
+        - ~
+        "#);
+    }
+
+    #[test]
+    fn finding_prs_already_handed_over_works() {
+        // GIVEN
+        let responses = [
+            serde_json::json!({
+                "data": {
+                    "node": { "autoMergeRequest": { "enabledAt": "2025-06-01T10:00:00Z" }, "isInMergeQueue": false }
+                }
+            }),
+            serde_json::json!({
+                "data": {
+                    "node": { "autoMergeRequest": null, "isInMergeQueue": true }
+                }
+            }),
+            serde_json::json!({
+                "data": {
+                    "node": { "autoMergeRequest": null, "isInMergeQueue": false }
+                }
+            }),
+            serde_json::json!({ "data": { "node": null } }),
+        ];
+
+        // WHEN
+        let results = responses.map(|r| is_handed_over(&r));
+
+        // THEN
+        assert_eq!(results, [true, true, false, false]);
+    }
+
+    #[test]
+    fn converting_commit_statuses_to_checks_works() {
+        // GIVEN
+        let statuses: Vec<Status> = serde_json::from_str(
+            r#"[
+                { "state": "success", "context": "ci/jenkins" },
+                { "state": "failure", "context": "ci/circleci: test" },
+                { "state": "error", "context": "buildkite/mrj" },
+                { "state": "pending", "context": "ci/circleci: lint" },
+                { "state": "success" }
+            ]"#,
+        )
+        .expect("statuses should've been parsed");
+
+        // WHEN
+        let checks = statuses
+            .iter()
+            .filter_map(Check::from_status)
+            .map(|c| (c.name, c.conclusion))
+            .collect::<Vec<_>>();
+
+        // THEN
+        assert_yaml_snapshot!(checks, @r#"
+        - - ci/jenkins
+          - success
+        - - "ci/circleci: test"
+          - failure
+        - - buildkite/mrj
+          - error
+        - - "ci/circleci: lint"
+          - ~
+        "#);
+    }
+
+    #[test]
+    fn detecting_last_page_works() {
+        // GIVEN
+        let cases = [
+            (100, 100, 250),
+            (100, 200, 250),
+            (50, 250, 250),
+            (100, 300, 300),
+            (0, 0, 0),
+        ];
+
+        // WHEN
+        let results = cases.map(|(fetched, so_far, total)| is_last_page(fetched, so_far, total));
+
+        // THEN
+        assert_eq!(results, [false, false, true, true, true]);
+    }
+
+    #[test]
+    fn ignoring_checks_matches_exact_names_globs_and_regexes() {
+        // GIVEN
+        let settings = settings(r#"ignored_checks = ["Vercel", "codecov/*", "re:preview-.+"]"#);
+
+        // WHEN
+        let results = [
+            "Vercel",
+            "codecov/patch",
+            "codecov/project",
+            "preview-docs",
+            "test",
+            "codecov",
+            "ci",
+            "codecov//",
+            "Vercel Preview",
+        ]
+        .map(|name| (name, is_check_ignored(&settings, name)));
+
+        // THEN
+        assert_yaml_snapshot!(results, @"
+        - - Vercel
+          - true
+        - - codecov/patch
+          - true
+        - - codecov/project
+          - true
+        - - preview-docs
+          - true
+        - - test
+          - false
+        - - codecov
+          - false
+        - - ci
+          - false
+        - - codecov//
+          - false
+        - - Vercel Preview
+          - false
+        ");
+    }
+
+    #[test]
+    fn evaluating_labels_qualifies_required_labels() {
+        // GIVEN
+        let settings = settings(
+            r#"
+required_labels = ["dependencies", "rust"]
+blocking_labels = ["do-not-merge"]
+"#,
+        );
+
+        // WHEN
+        let result = evaluate_labels(&settings, &["Dependencies", "rust", "ci"], None);
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Ok:
+          - Label: dependencies
+          - Label: rust
+        ");
+    }
+
+    #[test]
+    fn evaluating_labels_disqualifies_missing_required_label() {
+        // GIVEN
+        let settings = settings(r#"required_labels = ["dependencies", "rust"]"#);
+
+        // WHEN
+        let result = evaluate_labels(&settings, &["dependencies"], None);
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Err:
+          RequiredLabelMissing: rust
+        ");
+    }
+
+    #[test]
+    fn evaluating_labels_disqualifies_blocking_label() {
+        // GIVEN
+        let settings = settings(
+            r#"
+required_labels = ["dependencies"]
+blocking_labels = ["do-not-merge", "mrj:skip"]
+"#,
+        );
+
+        // WHEN
+        let result = evaluate_labels(&settings, &["dependencies", "MRJ:skip"], None);
+
+        // THEN
+        assert_yaml_snapshot!(result, @r#"
+        Err:
+          BlockingLabel: "mrj:skip"
+        "#);
+    }
+
+    #[test]
+    fn evaluating_labels_disqualifies_when_merges_are_frozen() {
+        // GIVEN
+        let settings = settings(r#"freeze_label = "merge-freeze""#);
+        let frozen_by = ("merge-freeze".to_string(), 12);
+
+        // WHEN
+        let result = evaluate_labels(&settings, &["dependencies"], Some(&frozen_by));
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Err:
+          Frozen:
+            label: merge-freeze
+            pr: 12
+        ");
+    }
+
+    #[test]
+    fn freezes_apply_to_search_hits_without_the_freeze_label() {
+        // GIVEN
+        // a search for Dependabot's PRs doesn't return the PR with the freeze label, so the
+        // freeze is looked up on its own
+        let settings = settings(r#"freeze_label = "merge-freeze""#);
+        let labelled = [issue(7, false), issue(12, true)];
+        let hit_labels = ["dependencies"];
+
+        // WHEN
+        let frozen_by = freezing_pr(&labelled).map(|pr| ("merge-freeze".to_string(), pr));
+        let result = evaluate_labels(&settings, &hit_labels, frozen_by.as_ref());
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Err:
+          Frozen:
+            label: merge-freeze
+            pr: 12
+        ");
+    }
+
+    #[test]
+    fn finding_approval_uses_latest_review_of_each_reviewer() {
+        // GIVEN
+        let settings = settings(
+            r#"
+require_approval = true
+trusted_reviewers = ["alice", "bob"]
+"#,
+        );
+        let reviews = vec![
+            review("alice", ReviewState::Approved),
+            review("alice", ReviewState::ChangesRequested),
+            review("bob", ReviewState::ChangesRequested),
+            review("bob", ReviewState::Approved),
+            review("bob", ReviewState::Commented),
+        ];
+
+        // WHEN
+        let result = approved_by(&settings, &reviews, PR_SHA);
+
+        // THEN
+        assert_eq!(result.as_deref(), Some("bob"));
+    }
+
+    #[test]
+    fn finding_approval_ignores_dismissed_and_untrusted_approvals() {
+        // GIVEN
+        let settings = settings(
+            r#"
+require_approval = true
+trusted_reviewers = ["alice", "bob"]
+"#,
+        );
+        let reviews = vec![
+            review("alice", ReviewState::Approved),
+            review("alice", ReviewState::Dismissed),
+            review("mallory", ReviewState::Approved),
+        ];
+
+        // WHEN
+        let result = approved_by(&settings, &reviews, PR_SHA);
+
+        // THEN
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn finding_approval_ignores_approvals_of_earlier_commits() {
+        // GIVEN
+        let settings = settings(
+            r#"
+require_approval = true
+trusted_reviewers = ["alice", "bob"]
+"#,
+        );
+        let reviews = vec![
+            review_of("alice", ReviewState::Approved, "0ld5ha"),
+            review_of("bob", ReviewState::Approved, PR_SHA),
+            review_of("bob", ReviewState::Commented, "0ld5ha"),
+        ];
+
+        // WHEN
+        let result = approved_by(&settings, &reviews, PR_SHA);
+        let result_after_push = approved_by(&settings, &reviews, "n3w5ha");
+
+        // THEN
+        assert_eq!(result.as_deref(), Some("bob"));
+        assert!(result_after_push.is_none());
+    }
+
+    #[test]
+    fn finding_approval_trusts_nobody_without_trusted_reviewers() {
+        // GIVEN
+        let settings = settings("require_approval = true");
+        let reviews = vec![review("alice", ReviewState::Approved)];
+
+        // WHEN
+        let result = approved_by(&settings, &reviews, PR_SHA);
+
+        // THEN
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn own_approvals_are_recognised_until_the_head_changes_or_they_are_dismissed() {
+        // GIVEN
+        let reviews = vec![
+            review_of("mrj-bot", ReviewState::Approved, PR_SHA),
+            review_of("alice", ReviewState::Approved, PR_SHA),
+            review_of("alice", ReviewState::Dismissed, PR_SHA),
+        ];
+
+        // WHEN
+        let approved = has_approved(&reviews, "mrj-bot", PR_SHA);
+        let approved_after_push = has_approved(&reviews, "mrj-bot", "n3w5ha");
+        let approved_after_dismissal = has_approved(&reviews, "alice", PR_SHA);
+
+        // THEN
+        assert!(approved);
+        assert!(!approved_after_push);
+        assert!(!approved_after_dismissal);
+    }
+
+    #[test]
+    fn an_old_release_date_doesnt_cut_a_fresh_prs_cool_down_short() {
+        // GIVEN
+        let settings = settings(r#"min_pr_age = "72h""#);
+        let min_age = settings.min_pr_age.expect("min_pr_age should've been set");
+        let pushed_at = at(2025, 3, 1, 12, 0);
+        let released_at = at(2020, 1, 1, 0, 0);
+
+        // WHEN
+        let aged_since = cool_down_start(pushed_at, Some(released_at));
+        let result = evaluate_pr_age(&min_age, aged_since, at(2025, 3, 2, 12, 0));
+
+        // THEN
+        assert_eq!(aged_since, pushed_at);
+        assert!(matches!(result, Err(DQ::CoolingDown { .. })));
+    }
+
+    #[test]
+    fn a_later_release_date_extends_a_prs_cool_down() {
+        // GIVEN
+        let pushed_at = at(2025, 3, 1, 12, 0);
+        let released_at = at(2025, 3, 2, 0, 0);
+
+        // WHEN
+        let results = [
+            cool_down_start(pushed_at, Some(released_at)),
+            cool_down_start(pushed_at, None),
+        ];
+
+        // THEN
+        assert_eq!(results, [released_at, pushed_at]);
+    }
+
+    #[test]
+    fn evaluating_pr_age_passes_once_min_age_has_passed() {
+        // GIVEN
+        let settings = settings(r#"min_pr_age = "72h""#);
+        let min_age = settings.min_pr_age.expect("min_pr_age should've been set");
+        let aged_since = at(2025, 3, 1, 12, 0);
+
+        // WHEN
+        let result = evaluate_pr_age(&min_age, aged_since, at(2025, 3, 4, 12, 0));
+
+        // THEN
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn evaluating_pr_age_disqualifies_pr_that_is_too_new() {
+        // GIVEN
+        let settings = settings(r#"min_pr_age = "72h""#);
+        let min_age = settings.min_pr_age.expect("min_pr_age should've been set");
+        let aged_since = at(2025, 3, 1, 12, 0);
+
+        // WHEN
+        let result = evaluate_pr_age(&min_age, aged_since, at(2025, 3, 2, 18, 48));
+
+        // THEN
+        assert_yaml_snapshot!(result, @r#"
+        Err:
+          CoolingDown:
+            eligible_at: "2025-03-04T12:00:00Z"
+            remaining: 1d17h12m
+        "#);
+    }
+
+    #[test]
+    fn evaluating_staleness_passes_for_recent_pr() {
+        // GIVEN
+        let settings = settings(r#"max_pr_age = "30d""#);
+        let max_age = settings.max_pr_age.expect("max_pr_age should've been set");
+
+        // WHEN
+        let result = evaluate_staleness(&max_age, at(2025, 3, 1, 12, 0), at(2025, 3, 30, 12, 0));
+
+        // THEN
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn evaluating_staleness_disqualifies_old_pr() {
+        // GIVEN
+        let settings = settings(r#"max_pr_age = "30d""#);
+        let max_age = settings.max_pr_age.expect("max_pr_age should've been set");
+
+        // WHEN
+        let result = evaluate_staleness(&max_age, at(2025, 3, 1, 12, 0), at(2025, 3, 31, 12, 0));
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Err:
+          Stale:
+            max_age: 30d
+        ");
+    }
+
+    #[test]
+    fn evaluating_diff_size_passes_within_limits() {
+        // GIVEN
+        let settings = settings(
+            r#"
+max_changed_files = 2
+max_additions = 100
+"#,
+        );
+
+        // WHEN
+        let result = evaluate_diff_size(&settings, Some(2), Some(100));
+
+        // THEN
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn evaluating_diff_size_disqualifies_prs_over_the_limits() {
+        // GIVEN
+        let settings = settings(
+            r#"
+max_changed_files = 2
+max_additions = 100
+"#,
+        );
+
+        // WHEN
+        let results = [
+            evaluate_diff_size(&settings, Some(3), Some(10)),
+            evaluate_diff_size(&settings, Some(1), Some(101)),
+        ];
+
+        // THEN
+        assert_yaml_snapshot!(results, @"
+        - Err:
+            TooManyChangedFiles:
+              found: 3
+              max: 2
+        - Err:
+            TooManyAdditions:
+              found: 101
+              max: 100
+        ");
+    }
+
+    #[test]
+    fn evaluating_paths_qualifies_allowed_paths() {
+        // GIVEN
+        let settings =
+            settings(r#"allowed_paths = ["**/Cargo.toml", "Cargo.lock", ".github/workflows/*"]"#);
+        let paths = [
+            "Cargo.toml",
+            "crates/core/Cargo.toml",
+            "Cargo.lock",
+            ".github/workflows/ci.yml",
+        ]
+        .map(String::from);
+
+        // WHEN
+        let result = evaluate_paths(&settings, &paths);
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Ok:
+          AllowedPaths: 4
+        ");
+    }
+
+    #[test]
+    fn evaluating_paths_lists_paths_that_arent_allowed() {
+        // GIVEN
+        let settings =
+            settings(r#"allowed_paths = ["Cargo.toml", "Cargo.lock", ".github/workflows/*"]"#);
+        let paths = [
+            "Cargo.toml",
+            "src/main.rs",
+            ".github/workflows/nested/ci.yml",
+            "crates/core/Cargo.toml",
+        ]
+        .map(String::from);
+
+        // WHEN
+        let result = evaluate_paths(&settings, &paths);
+
+        // THEN
+        assert_yaml_snapshot!(result, @r#"
+        Err:
+          PathsNotAllowed:
+            - src/main.rs
+            - ".github/workflows/nested/ci.yml"
+            - crates/core/Cargo.toml
+        "#);
+    }
+
+    #[test]
+    fn evaluating_rules_goes_with_the_first_match() {
+        // GIVEN
+        let settings = settings(
+            r#"
+[[rules]]
+name = "no security fixes"
+rule = 'deny if title contains "[security]"'
+
+[[rules]]
+name = "patch bumps"
+rule = 'allow if update_type == "patch" && ecosystem == "cargo"'
+"#,
+        );
+        let patch = PrAttributes {
+            title: "build: bump clap from 4.5.39 to 4.5.40".to_string(),
+            ecosystem: Some("cargo".to_string()),
+            update_type: Some(UpdateType::Patch),
+            ..PrAttributes::default()
+        };
+        let security_patch = PrAttributes {
+            title: "[security] build: bump openssl from 0.10.72 to 0.10.73".to_string(),
+            ecosystem: Some("cargo".to_string()),
+            update_type: Some(UpdateType::Patch),
+            ..PrAttributes::default()
+        };
+        let minor = PrAttributes {
+            title: "build: bump clap from 4.5.39 to 4.6.0".to_string(),
+            ecosystem: Some("cargo".to_string()),
+            update_type: Some(UpdateType::Minor),
+            ..PrAttributes::default()
+        };
+
+        // WHEN
+        let results = [&patch, &security_patch, &minor]
+            .map(|attributes| evaluate_rules(&settings.rules, attributes));
+
+        // THEN
+        assert_yaml_snapshot!(results, @"
+        - Ok:
+            Rule: patch bumps
+        - Err:
+            Rule: no security fixes
+        - Err: NoRuleMatched
+        ");
+    }
+
+    #[test]
+    fn evaluating_only_deny_rules_lets_other_prs_through() {
+        // GIVEN
+        let settings = settings(
+            r#"
+[[rules]]
+name = "big diffs"
+rule = 'deny if changed_files > 10 || labels contains "needs-review"'
+"#,
+        );
+        let attributes = PrAttributes {
+            changed_files: Some(2),
+            labels: vec!["dependencies".to_string()],
+            ..PrAttributes::default()
+        };
+
+        // WHEN
+        let result = evaluate_rules(&settings.rules, &attributes);
+
+        // THEN
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn evaluating_deny_rules_catches_prs_with_unknown_values() {
+        // GIVEN
+        let settings = settings(
+            r#"
+[[rules]]
+name = "anything but patch bumps"
+rule = 'deny if update_type != "patch"'
+"#,
+        );
+        let attributes = PrAttributes {
+            title: "Update README.md".to_string(),
+            update_type: None,
+            ..PrAttributes::default()
+        };
+
+        // WHEN
+        let result = evaluate_rules(&settings.rules, &attributes);
+
+        // THEN
+        assert_yaml_snapshot!(result, @"
+        Err:
+          Rule: anything but patch bumps
+        ");
+    }
+
+    #[test]
+    fn finding_latest_rebase_request_ignores_other_comments() {
+        // GIVEN
+        let comments = vec![
+            comment("mrj-bot", "@dependabot rebase", at(2025, 3, 1, 10, 0)),
+            comment("mrj-bot", "@dependabot rebase\n", at(2025, 3, 2, 10, 0)),
+            comment("mrj-bot", "@dependabot recreate", at(2025, 3, 3, 10, 0)),
+            comment("alice", "@dependabot rebase", at(2025, 3, 4, 10, 0)),
+        ];
+
+        // WHEN
+        let result = latest_rebase_request(&comments, Some("mrj-bot"));
+        let result_without_login = latest_rebase_request(&comments, None);
+
+        // THEN
+        assert_eq!(result, Some(at(2025, 3, 2, 10, 0)));
+        assert_eq!(result_without_login, Some(at(2025, 3, 4, 10, 0)));
+    }
+
+    #[test]
+    fn finding_latest_rebase_request_returns_none_if_never_requested() {
+        // GIVEN
+        let comments = vec![comment(
+            "mrj-bot",
+            "@dependabot squash and merge",
+            at(2025, 3, 1, 10, 0),
+        )];
+
+        // WHEN
+        let result = latest_rebase_request(&comments, Some("mrj-bot"));
+
+        // THEN
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn rebase_requests_stop_being_pending_once_acted_on_or_expired() {
+        // GIVEN
+        let requested_at = at(2025, 3, 2, 10, 0);
+        let cases = [
+            // not acted on yet
+            (at(2025, 3, 1, 10, 0), at(2025, 3, 2, 12, 0)),
+            // acted on
+            (at(2025, 3, 2, 10, 5), at(2025, 3, 2, 12, 0)),
+            // not acted on for too long
+            (at(2025, 3, 1, 10, 0), at(2025, 3, 3, 10, 0)),
+        ];
+
+        // WHEN
+        let results = cases.map(|(pushed_at, now)| rebase_pending(requested_at, pushed_at, now));
+
+        // THEN
+        assert_eq!(results, [true, false, false]);
+    }
+
+    #[test]
+    fn ticking_rebase_checkbox_works() {
+        // GIVEN
+        let body = r#"---
+
+### Configuration
+
+- [ ] <!-- rebase-check -->If you want to rebase/retry this PR, check this box
+
+---
+"#;
+
+        // WHEN
+        let result = tick_rebase_checkbox(body);
+
+        // THEN
+        assert_eq!(
+            result.as_deref(),
+            Some(
+                r#"---
+
+### Configuration
+
+- [x] <!-- rebase-check -->If you want to rebase/retry this PR, check this box
+
+---
+"#
+            )
+        );
+    }
+
+    #[test]
+    fn ticking_rebase_checkbox_fails_without_an_unticked_checkbox() {
+        // GIVEN
+        let bodies = [
+            "- [x] <!-- rebase-check -->If you want to rebase/retry this PR, check this box",
+            "Bumps clap from 4.5.39 to 4.5.40.",
+        ];
+
+        // WHEN
+        // THEN
+        for body in bodies {
+            assert!(tick_rebase_checkbox(body).is_none(), "body: {body}");
+        }
+    }
+
+    fn settings(extra: &str) -> RepoSettings {
+        config(extra).settings_for(&Repo {
+            owner: "dhth".to_string(),
+            repo: "mrj".to_string(),
+        })
+    }
+
+    fn check(name: &str, conclusion: Option<&str>) -> Check {
+        Check {
+            name: name.to_string(),
+            conclusion: conclusion.map(str::to_string),
+        }
+    }
+
+    fn review(reviewer: &str, state: ReviewState) -> (String, ReviewState, Option<String>) {
+        review_of(reviewer, state, PR_SHA)
+    }
+
+    fn review_of(
+        reviewer: &str,
+        state: ReviewState,
+        commit_id: &str,
+    ) -> (String, ReviewState, Option<String>) {
+        (reviewer.to_string(), state, Some(commit_id.to_string()))
+    }
+
+    fn issue(number: u64, is_pr: bool) -> Issue {
+        let url = format!("https://api.github.com/repos/dhth/mrj/issues/{number}");
+        let mut issue = serde_json::json!({
+            "id": number,
+            "node_id": format!("I_{number}"),
+            "url": url,
+            "repository_url": "https://api.github.com/repos/dhth/mrj",
+            "labels_url": format!("{url}/labels{{/name}}"),
+            "comments_url": format!("{url}/comments"),
+            "events_url": format!("{url}/events"),
+            "html_url": format!("https://github.com/dhth/mrj/issues/{number}"),
+            "number": number,
+            "state": "open",
+            "title": "Freeze merges",
+            "user": {
+                "login": "someone",
+                "id": 1,
+                "node_id": "U_1",
+                "avatar_url": "https://avatars.githubusercontent.com/u/1",
+                "gravatar_id": "",
+                "url": "https://api.github.com/users/someone",
+                "html_url": "https://github.com/someone",
+                "followers_url": "https://api.github.com/users/someone/followers",
+                "following_url": "https://api.github.com/users/someone/following{/other_user}",
+                "gists_url": "https://api.github.com/users/someone/gists{/gist_id}",
+                "starred_url": "https://api.github.com/users/someone/starred{/owner}{/repo}",
+                "subscriptions_url": "https://api.github.com/users/someone/subscriptions",
+                "organizations_url": "https://api.github.com/users/someone/orgs",
+                "repos_url": "https://api.github.com/users/someone/repos",
+                "events_url": "https://api.github.com/users/someone/events{/privacy}",
+                "received_events_url": "https://api.github.com/users/someone/received_events",
+                "type": "User",
+                "site_admin": false
+            },
+            "labels": [],
+            "assignees": [],
+            "locked": false,
+            "comments": 0,
+            "created_at": "2025-06-01T10:00:00Z",
+            "updated_at": "2025-06-01T10:00:00Z"
+        });
+        if is_pr {
+            issue["pull_request"] = serde_json::json!({
+                "url": format!("https://api.github.com/repos/dhth/mrj/pulls/{number}"),
+                "html_url": format!("https://github.com/dhth/mrj/pull/{number}"),
+                "diff_url": format!("https://github.com/dhth/mrj/pull/{number}.diff"),
+                "patch_url": format!("https://github.com/dhth/mrj/pull/{number}.patch")
+            });
+        }
+
+        serde_json::from_value(issue).expect("issue should've been deserialized")
+    }
+
+    fn comment(
+        author: &str,
+        body: &str,
+        created_at: DateTime<Utc>,
+    ) -> (String, String, DateTime<Utc>) {
+        (author.to_string(), body.to_string(), created_at)
+    }
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        use chrono::TimeZone;
+
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .single()
+            .expect("date should've been valid")
+    }
+
+    fn upgrade(title: &str) -> Option<Upgrade> {
+        Upgrade::parse(title, None, "")
+    }
+
+    fn upgrade_pr<'a>(
+        upgrade: &'a Upgrade,
+        head: &'a str,
+        created_at: Option<DateTime<Utc>>,
+    ) -> UpgradePr<'a> {
+        UpgradePr {
+            upgrade,
+            base: "main",
+            head,
+            author: Some("dependabot[bot]"),
+            created_at,
+        }
+    }
+
+    fn upgrade_from_head(title: &str, head_ref: &str) -> Upgrade {
+        Upgrade::parse(title, None, head_ref).expect("upgrade should've been parsed")
+    }
 }
@@ -4,8 +4,10 @@ use crate::domain::{
     RunSummary,
 };
 use crate::merge::RunBehaviours;
+use crate::merge::discover::discover_repos;
 use crate::merge::log::RunLogger;
-use crate::merge::process::merge_pr_for_repo;
+use crate::merge::process::{Candidates, Clients, merge_pr_for_repo};
+use crate::merge::search::search_prs;
 use anyhow::Context;
 use chrono::Utc;
 use futures::StreamExt;
@@ -18,6 +20,7 @@ const MAX_FETCH_TASKS: usize = 50;
 
 pub(crate) async fn merge_prs(
     client: Arc<Octocrab>,
+    approver: Arc<Octocrab>,
     config: Arc<Config>,
     repos_override: Vec<Repo>,
     behaviours: RunBehaviours,
@@ -25,11 +28,30 @@ pub(crate) async fn merge_prs(
     let mut logger = RunLogger::new(std::io::stdout(), &behaviours);
     let mut results = vec![];
 
-    let repos_to_use = if repos_override.is_empty() {
-        config.repos.clone()
-    } else {
+    let targets = if !repos_override.is_empty() {
         repos_override
+            .into_iter()
+            .map(|repo| (repo, Candidates::OpenPRs))
+            .collect::<Vec<_>>()
+    } else if let Some(query) = &config.search {
+        search_prs(client.as_ref(), config.as_ref(), query)
+            .await
+            .context("couldn't search for PRs to run for")?
+            .into_iter()
+            .map(|(repo, hits)| (repo, Candidates::Found(hits)))
+            .collect()
+    } else {
+        discover_repos(client.as_ref(), config.as_ref())
+            .await
+            .context("couldn't discover repos to run for")?
+            .into_iter()
+            .map(|repo| (repo, Candidates::OpenPRs))
+            .collect()
     };
+    let repos_to_use = targets
+        .iter()
+        .map(|(repo, _)| repo.clone())
+        .collect::<Vec<_>>();
 
     if repos_to_use.is_empty() {
         return Ok(None);
@@ -37,16 +59,31 @@ pub(crate) async fn merge_prs(
 
     let started_at = Utc::now();
     logger.print_banner();
-    logger.print_startup_info(config.as_ref(), started_at);
+    logger.print_startup_info(config.as_ref(), &repos_to_use, started_at);
+
+    let clients = Clients {
+        login: login_of(client.as_ref()).await,
+        approver_login: login_of(approver.as_ref()).await,
+        client,
+        approver,
+    };
 
     let semaphore = Arc::new(Semaphore::new(MAX_FETCH_TASKS));
     let mut futures = FuturesUnordered::new();
-    for repo in repos_to_use {
+    for (repo, candidates) in targets {
         let semaphore = Arc::clone(&semaphore);
-        let client = Arc::clone(&client);
+        let clients = clients.clone();
         let config = Arc::clone(&config);
         futures.push(tokio::task::spawn(async move {
-            merge_pr_for_repo(semaphore, client, config.as_ref(), repo, behaviours.execute).await
+            merge_pr_for_repo(
+                semaphore,
+                clients,
+                config.as_ref(),
+                repo,
+                candidates,
+                behaviours.execute,
+            )
+            .await
         }));
     }
 
@@ -69,6 +106,7 @@ pub(crate) async fn merge_prs(
         .context("couldn't write output to file")?;
 
     Ok(Some(RunMergeResults {
+        selected_repos: repos_to_use,
         results,
         summary,
         started_at,
@@ -76,6 +114,11 @@ pub(crate) async fn merge_prs(
     }))
 }
 
+/// Looks up the login of the user a client acts as; `None` if it can't be looked up.
+async fn login_of(client: &Octocrab) -> Option<String> {
+    client.current().user().await.ok().map(|user| user.login)
+}
+
 fn filter_repo_result(result: RepoResult, behaviours: &RunBehaviours) -> Option<RepoResult> {
     match result {
         RepoResult::Errored(repo_check) => Some(RepoResult::Errored(repo_check)),
@@ -107,6 +150,8 @@ fn should_show_merge_result(result: &MergeResult, behaviours: &RunBehaviours) ->
         MergeResult::Disqualified(pr_check) => match pr_check.state.reason() {
             Disqualification::Author(_) if !behaviours.show_prs_from_untrusted_authors => false,
             Disqualification::Head(_) if !behaviours.show_prs_with_unmatched_head => false,
+            Disqualification::Draft if !behaviours.show_draft_prs => false,
+            Disqualification::Stale { .. } if !behaviours.show_stale_prs => false,
             _ => true,
         },
         _ => true,
@@ -116,14 +161,18 @@ fn should_show_merge_result(result: &MergeResult, behaviours: &RunBehaviours) ->
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{PRCheck, PRCheckErrored, PRCheckFinished, PRDisqualified, Qualification};
+    use crate::domain::{
+        HumanDuration, PRCheck, PRCheckErrored, PRCheckFinished, PRDisqualified, Qualification,
+    };
     use insta::assert_yaml_snapshot;
+    use std::time::Duration;
 
     const OWNER: &str = "dhth";
     const REPO: &str = "mrj";
     const PR_TITLE: &str = "build: bump clap from 4.5.39 to 4.5.40";
     const PR_URL: &str = "https://github.com/dhth/mrj/pull/1";
     const PR_HEAD: &str = "dependabot/cargo/clap-4.5.40";
+    const PR_SHA: &str = "9f2c1e4b7a3d8c6e5f0a1b2c3d4e5f6a7b8c9d0e";
 
     #[test]
     fn filtering_repo_result_keeps_qualified_prs() {
@@ -149,9 +198,14 @@ mod tests {
                 url: "https://github.com/dhth/mrj/pull/1"
                 pr_created_at: ~
                 pr_updated_at: ~
+                upgrade: ~
+                ecosystem: ~
                 qualifications:
                   - Head: dependabot/cargo/clap-4.5.40
-                state: ~
+                state:
+                  head_sha: 9f2c1e4b7a3d8c6e5f0a1b2c3d4e5f6a7b8c9d0e
+                  merge_commit_sha: ~
+                  post_merge: []
         "#
         );
     }
@@ -180,6 +234,8 @@ mod tests {
                 url: "https://github.com/dhth/mrj/pull/1"
                 pr_created_at: ~
                 pr_updated_at: ~
+                upgrade: ~
+                ecosystem: ~
                 qualifications:
                   - Head: dependabot/cargo/clap-4.5.40
                 state: "couldn't merge PR: GitHub API was down"
@@ -244,6 +300,8 @@ mod tests {
                 url: "https://github.com/dhth/mrj/pull/1"
                 pr_created_at: ~
                 pr_updated_at: ~
+                upgrade: ~
+                ecosystem: ~
                 qualifications: []
                 state:
                   Head: big-refactor
@@ -276,6 +334,8 @@ mod tests {
                 url: "https://github.com/dhth/mrj/pull/1"
                 pr_created_at: ~
                 pr_updated_at: ~
+                upgrade: ~
+                ecosystem: ~
                 qualifications:
                   - Head: dependabot/cargo/clap-4.5.40
                 state:
@@ -284,6 +344,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn filtering_repo_result_filters_out_draft_and_stale_prs_by_default() {
+        // GIVEN
+        let result = RepoResult::Finished(RepoCheck {
+            owner: OWNER.to_string(),
+            name: REPO.to_string(),
+            state: RepoCheckFinished(vec![
+                merge_result_disqualified(5, Disqualification::Draft),
+                merge_result_disqualified_stale(),
+            ]),
+        });
+
+        // WHEN
+        let filtered_result = filter_repo_result(result, &RunBehaviours::default());
+
+        // THEN
+        assert!(filtered_result.is_none());
+    }
+
+    #[test]
+    fn filtering_repo_result_keeps_draft_and_stale_prs_when_requested() {
+        // GIVEN
+        let behaviours = RunBehaviours::default().show_draft_prs().show_stale_prs();
+        let result = RepoResult::Finished(RepoCheck {
+            owner: OWNER.to_string(),
+            name: REPO.to_string(),
+            state: RepoCheckFinished(vec![
+                merge_result_disqualified(5, Disqualification::Draft),
+                merge_result_disqualified_stale(),
+            ]),
+        });
+
+        // WHEN
+        let filtered_result = filter_repo_result(result, &behaviours);
+
+        // THEN
+        assert_yaml_snapshot!(filtered_result, @r#"
+        Finished:
+          owner: dhth
+          name: mrj
+          state:
+            - Disqualified:
+                number: 5
+                title: "build: bump clap from 4.5.39 to 4.5.40"
+                url: "https://github.com/dhth/mrj/pull/1"
+                pr_created_at: ~
+                pr_updated_at: ~
+                upgrade: ~
+                ecosystem: ~
+                qualifications:
+                  - Head: dependabot/cargo/clap-4.5.40
+                state: Draft
+            - Disqualified:
+                number: 6
+                title: "build: bump clap from 4.5.39 to 4.5.40"
+                url: "https://github.com/dhth/mrj/pull/1"
+                pr_created_at: ~
+                pr_updated_at: ~
+                upgrade: ~
+                ecosystem: ~
+                qualifications:
+                  - Head: dependabot/cargo/clap-4.5.40
+                state:
+                  Stale:
+                    max_age: 30d
+        "#);
+    }
+
     #[test]
     fn filtering_repo_result_keeps_repo_with_empty_results_when_requested() {
         // GIVEN
@@ -339,6 +467,8 @@ mod tests {
                 url: "https://github.com/dhth/mrj/pull/1"
                 pr_created_at: ~
                 pr_updated_at: ~
+                upgrade: ~
+                ecosystem: ~
                 qualifications:
                   - Head: dependabot/cargo/clap-4.5.40
                 state: "couldn't merge PR: GitHub API was down"
@@ -348,13 +478,41 @@ mod tests {
                 url: "https://github.com/dhth/mrj/pull/1"
                 pr_created_at: ~
                 pr_updated_at: ~
+                upgrade: ~
+                ecosystem: ~
                 qualifications:
                   - Head: dependabot/cargo/clap-4.5.40
-                state: ~
+                state:
+                  head_sha: 9f2c1e4b7a3d8c6e5f0a1b2c3d4e5f6a7b8c9d0e
+                  merge_commit_sha: ~
+                  post_merge: []
         "#
         );
     }
 
+    fn merge_result_disqualified(number: u64, dq: Disqualification) -> MergeResult {
+        MergeResult::Disqualified(PRCheck {
+            number,
+            title: PR_TITLE.to_string(),
+            url: PR_URL.to_string(),
+            pr_created_at: None,
+            pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
+            qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
+            state: PRDisqualified(dq),
+        })
+    }
+
+    fn merge_result_disqualified_stale() -> MergeResult {
+        merge_result_disqualified(
+            6,
+            Disqualification::Stale {
+                max_age: HumanDuration::from(Duration::from_secs(30 * 24 * 60 * 60)),
+            },
+        )
+    }
+
     fn merge_result_disqualified_unmatched_head() -> MergeResult {
         MergeResult::Disqualified(PRCheck {
             number: 1,
@@ -362,6 +520,8 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![],
             state: PRDisqualified(Disqualification::Head("big-refactor".to_string())),
         })
@@ -374,6 +534,8 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
             state: PRDisqualified(Disqualification::Author(Some(
                 "untrusted-author".to_string(),
@@ -388,8 +550,14 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
-            state: PRCheckFinished,
+            state: PRCheckFinished {
+                head_sha: PR_SHA.to_string(),
+                merge_commit_sha: None,
+                post_merge: vec![],
+            },
         })
     }
 
@@ -400,6 +568,8 @@ mod tests {
             url: PR_URL.to_string(),
             pr_created_at: None,
             pr_updated_at: None,
+            upgrade: None,
+            ecosystem: None,
             qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
             state: PRCheckErrored(anyhow::anyhow!("couldn't merge PR: GitHub API was down")),
         })
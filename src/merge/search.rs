@@ -0,0 +1,212 @@
+use crate::config::Config;
+use crate::domain::Repo;
+use anyhow::Context;
+use octocrab::Octocrab;
+use octocrab::models::IssueState;
+use octocrab::models::issues::Issue;
+
+/// A PR found via a search query, along with what the search result already tells about it; it
+/// lets PRs that can't qualify be left out without fetching them.
+#[derive(Debug, Clone)]
+pub(super) struct SearchHit {
+    pub(super) repo: Repo,
+    pub(super) number: u64,
+    pub(super) open: bool,
+    pub(super) author: String,
+}
+
+impl SearchHit {
+    fn from_issue(issue: &Issue) -> Option<Self> {
+        // the search API returns issues as well, unless the query is limited to PRs
+        issue.pull_request.as_ref()?;
+
+        Some(Self {
+            repo: repo_from_api_path(issue.repository_url.path())?,
+            number: issue.number,
+            open: issue.state == IssueState::Open,
+            author: issue.user.login.clone(),
+        })
+    }
+}
+
+/// Finds candidate PRs via GitHub's search API, and groups them by repo.
+pub(super) async fn search_prs(
+    client: &Octocrab,
+    config: &Config,
+    query: &str,
+) -> anyhow::Result<Vec<(Repo, Vec<SearchHit>)>> {
+    let mut page = client
+        .search()
+        .issues_and_pull_requests(query)
+        .sort(config.sort_by.search_sort())
+        .order(config.sort_direction.search_order())
+        .per_page(100)
+        .send()
+        .await
+        .context("couldn't run search query")?;
+
+    let mut issues = std::mem::take(&mut page.items);
+
+    // results beyond the cap are left for the next run
+    for _ in 1..config.max_pages {
+        match client
+            .get_page::<Issue>(&page.next)
+            .await
+            .context("couldn't get next page of search results")?
+        {
+            Some(mut next_page) => {
+                issues.append(&mut next_page.items);
+                page = next_page;
+            }
+            None => break,
+        }
+    }
+
+    let hits = issues
+        .iter()
+        .filter_map(SearchHit::from_issue)
+        .collect::<Vec<_>>();
+
+    Ok(group_hits(config, hits))
+}
+
+pub(super) fn group_hits(config: &Config, hits: Vec<SearchHit>) -> Vec<(Repo, Vec<SearchHit>)> {
+    let mut grouped: Vec<(Repo, Vec<SearchHit>)> = vec![];
+
+    for hit in hits {
+        let full_name = hit.repo.to_string();
+        if config.exclude.iter().any(|g| g.is_match(&full_name)) {
+            continue;
+        }
+
+        match grouped.iter_mut().find(|(repo, _)| repo == &hit.repo) {
+            Some((_, repo_hits)) => {
+                if !repo_hits.iter().any(|h| h.number == hit.number) {
+                    repo_hits.push(hit);
+                }
+            }
+            None => grouped.push((hit.repo.clone(), vec![hit])),
+        }
+    }
+
+    grouped
+}
+
+/// Extracts the repo from the path of a repository's API URL, eg. "/repos/dhth/mrj".
+fn repo_from_api_path(path: &str) -> Option<Repo> {
+    let mut segments = path.trim_matches('/').split('/');
+
+    match (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) {
+        (Some("repos"), Some(owner), Some(repo), None) if !owner.is_empty() && !repo.is_empty() => {
+            Some(Repo {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merge::tests::config;
+    use insta::assert_yaml_snapshot;
+
+    #[test]
+    fn grouping_hits_preserves_search_order() {
+        // GIVEN
+        let config = config("");
+        let hits = vec![
+            hit("org", "service-a", 4),
+            hit("org", "service-b", 2),
+            hit("org", "service-a", 1),
+            hit("org", "service-a", 4),
+        ];
+
+        // WHEN
+        let grouped = numbers(group_hits(&config, hits));
+
+        // THEN
+        assert_yaml_snapshot!(grouped, @"
+        - - owner: org
+            repo: service-a
+          - - 4
+            - 1
+        - - owner: org
+            repo: service-b
+          - - 2
+        ");
+    }
+
+    #[test]
+    fn grouping_hits_applies_exclusions() {
+        // GIVEN
+        let config = config(r#"exclude = ["org/service-b"]"#);
+        let hits = vec![hit("org", "service-a", 1), hit("org", "service-b", 2)];
+
+        // WHEN
+        let grouped = numbers(group_hits(&config, hits));
+
+        // THEN
+        assert_yaml_snapshot!(grouped, @"
+        - - owner: org
+            repo: service-a
+          - - 1
+        ");
+    }
+
+    #[test]
+    fn parsing_repo_from_api_path_works() {
+        // GIVEN
+        // WHEN
+        let repo = repo_from_api_path("/repos/dhth/mrj");
+
+        // THEN
+        assert_yaml_snapshot!(repo, @"
+        owner: dhth
+        repo: mrj
+        ");
+    }
+
+    #[test]
+    fn parsing_repo_from_unexpected_api_path_fails() {
+        // GIVEN
+        let paths = [
+            "/users/dhth",
+            "/repos/dhth",
+            "/repos/dhth/mrj/pulls",
+            "/repos//mrj",
+        ];
+
+        // WHEN
+        // THEN
+        for path in paths {
+            assert!(repo_from_api_path(path).is_none(), "path: {path}");
+        }
+    }
+
+    fn numbers(grouped: Vec<(Repo, Vec<SearchHit>)>) -> Vec<(Repo, Vec<u64>)> {
+        grouped
+            .into_iter()
+            .map(|(repo, hits)| (repo, hits.iter().map(|h| h.number).collect()))
+            .collect()
+    }
+
+    fn hit(owner: &str, repo: &str, number: u64) -> SearchHit {
+        SearchHit {
+            repo: Repo {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            },
+            number,
+            open: true,
+            author: "dependabot[bot]".to_string(),
+        }
+    }
+}
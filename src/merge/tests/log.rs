@@ -1,11 +1,14 @@
 use super::super::behaviours::RunBehaviours;
 use super::super::log::RunLogger;
 use crate::domain::{
-    Disqualification, MergeResult, Qualification, RepoResult, RunDisqualification, RunSummary,
+    Disqualification, HumanDuration, MergeResult, Qualification, RepoResult, RunDisqualification,
+    RunSummary,
 };
 use crate::domain::{
-    PRCheck, PRCheckFinished, PRDisqualified, RepoCheck, RepoCheckErrored, RepoCheckFinished,
+    MergeStrategy, MergedPR, PRBranchUpdated, PRCheck, PRCheckFinished, PRDisqualified, PREnqueued,
+    PostMergeAction, RepoCheck, RepoCheckErrored, RepoCheckFinished,
 };
+use crate::upgrade::{UpdateBot, UpdateType};
 use chrono::{DateTime, TimeZone, Utc};
 use insta::assert_snapshot;
 
@@ -14,6 +17,7 @@ const REPO: &str = "mrj";
 const PR_TITLE: &str = "build: bump clap from 4.5.39 to 4.5.40";
 const PR_URL: &str = "https://github.com/dhth/mrj/pull/1";
 const PR_HEAD: &str = "dependabot/cargo/clap-4.5.40";
+const PR_SHA: &str = "9f2c1e4b7a3d8c6e5f0a1b2c3d4e5f6a7b8c9d0e";
 const PR_AUTHOR: &str = "dependabot[bot]";
 
 #[test]
@@ -341,6 +345,1141 @@ fn pr_with_an_undesirable_state_is_printed_correctly() {
     );
 }
 
+#[test]
+fn pr_with_a_disallowed_update_type_is_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_disqualified_major_update()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(
+        out,
+        @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.40 to 5.0.0
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-5.0.0" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ update ]   "clap" is a major update, which is not allowed ❌
+    "#
+    );
+}
+
+#[test]
+fn pr_with_a_denied_package_is_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_disqualified_denied_package()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(
+        out,
+        @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump openssl from 0.10.72 to 0.10.73
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/openssl-0.10.73" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ pkg    ]   "openssl" is in the list of denied packages ❌
+    "#
+    );
+}
+
+#[test]
+fn pr_for_a_disallowed_ecosystem_is_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_disqualified_ecosystem()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(
+        out,
+        @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build(deps): bump lodash from 4.17.20 to 4.17.21
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/npm_and_yarn/lodash-4.17.21" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ eco    ]   "npm_and_yarn" is not in the list of allowed ecosystems ❌
+    "#
+    );
+}
+
+#[test]
+fn pr_with_a_missing_required_check_is_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_disqualified_missing_required_check()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(
+        out,
+        @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   no check matching "test" was found, but it's required ❌
+    "#
+    );
+}
+
+#[test]
+fn pr_with_an_ignored_check_is_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_qualified_with_ignored_check()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(
+        out,
+        @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "codecov/patch" is ignored (concluded with "failure")
+            [ check  ]   "preview-docs" is ignored (hasn't concluded yet)
+            [ check  ]   "test" concluded with desired status: "success"
+            [ state  ]   "clean" is desirable
+            PR matches all criteria, I would've merged it if this weren't a dry run ✅
+    "#
+    );
+}
+
+#[test]
+fn pr_in_a_frozen_repo_is_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_disqualified_frozen()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(
+        out,
+        @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ label  ]   merges are frozen, as PR #12 has the label "merge-freeze" ❌
+    "#
+    );
+}
+
+#[test]
+fn pr_without_approval_is_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![
+            merge_result_disqualified_missing_approval(),
+            merge_result_qualified_with_auto_approval(),
+        ]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(
+        out,
+        @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" concluded with desired status: "success"
+            [ review ]   the PR hasn't been approved by a trusted reviewer ❌
+
+    -> checking PR #2
+            build: bump serde from 1.0.227 to 1.0.228
+            https://github.com/dhth/mrj/pull/2
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/serde-1.0.228" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" concluded with desired status: "success"
+            [ state  ]   "blocked" is desirable
+            [ review ]   nobody has approved the PR yet; I'll approve it before merging
+            PR matches all criteria, I would've merged it if this weren't a dry run ✅
+    "#
+    );
+}
+
+#[test]
+fn pr_that_is_cooling_down_is_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_disqualified_cooling_down()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ age    ]   the PR is too new; it'll be eligible in 1d17h1m (at 2024-01-04 01:01 UTC) ❌
+    "#);
+}
+
+#[test]
+fn draft_and_stale_prs_are_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![
+            merge_result_disqualified_after_author(1, Disqualification::Draft),
+            merge_result_disqualified_after_author(
+                2,
+                Disqualification::Stale {
+                    max_age: HumanDuration::from(std::time::Duration::from_secs(30 * 24 * 60 * 60)),
+                },
+            ),
+        ]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ draft  ]   the PR is a draft; skipping it until it's ready for review ❌
+
+    -> checking PR #2
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ age    ]   the PR is older than 30d; leaving it for a human to look at ❌
+    "#);
+}
+
+#[test]
+fn prs_with_disallowed_changes_are_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![
+            merge_result_disqualified_after_author(
+                1,
+                Disqualification::PathsNotAllowed(vec![
+                    "src/main.rs".to_string(),
+                    "build.rs".to_string(),
+                ]),
+            ),
+            merge_result_disqualified_after_author(
+                2,
+                Disqualification::TooManyChangedFiles { found: 12, max: 10 },
+            ),
+            merge_result_disqualified_after_author(
+                3,
+                Disqualification::TooManyAdditions {
+                    found: 1200,
+                    max: 1000,
+                },
+            ),
+        ]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ files  ]   these changed files don't match the allowed paths: src/main.rs, build.rs ❌
+
+    -> checking PR #2
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ files  ]   the PR changes 12 file(s), but at most 10 are allowed ❌
+
+    -> checking PR #3
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ files  ]   the PR adds 1200 line(s), but at most 1000 are allowed ❌
+    "#);
+}
+
+#[test]
+fn prs_whose_head_changed_are_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_disqualified_after_author(
+            1,
+            Disqualification::HeadChanged,
+        )]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ head  ]   the PR's head changed while I was evaluating it ❌
+    "#);
+}
+
+#[test]
+fn prs_with_rebase_requests_are_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let behaviours = RunBehaviours {
+        execute: true,
+        ..RunBehaviours::default()
+    };
+    let mut l = RunLogger::new(&mut buffer, &behaviours);
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![
+            merge_result_disqualified_after_author(
+                1,
+                Disqualification::RebaseRequested(UpdateBot::Dependabot),
+            ),
+            merge_result_disqualified_after_author(
+                2,
+                Disqualification::RebasePending {
+                    bot: UpdateBot::Dependabot,
+                    requested_at: Some(updated_at()),
+                },
+            ),
+            merge_result_disqualified_after_author(
+                3,
+                Disqualification::RebasePending {
+                    bot: UpdateBot::Renovate,
+                    requested_at: None,
+                },
+            ),
+        ]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ rebase ]   the PR has merge conflicts; I've asked dependabot to rebase it ❌
+
+    -> checking PR #2
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ rebase ]   the PR has merge conflicts; dependabot was asked to rebase it at 2024-01-02 01:01 UTC, and hasn't done so yet ❌
+
+    -> checking PR #3
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ rebase ]   the PR has merge conflicts; renovate was asked to rebase it, and hasn't done so yet ❌
+    "#);
+}
+
+#[test]
+fn prs_merged_in_slots_are_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![
+            merge_result_qualified_in_slot(1, 1),
+            merge_result_disqualified_failed_check(),
+            merge_result_qualified_in_slot(3, 2),
+        ]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" concluded with desired status: "success"
+            [ state  ]   "clean" is desirable
+            [ slot   ]   this is merge 1 of up to 3 for this repo
+            PR matches all criteria, I would've merged it if this weren't a dry run ✅
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "build (macos-latest)" concluded with desired status: "success"
+            [ check  ]   "build (ubuntu-latest)" concluded with desired status: "success"
+            [ check  ]   "test" concluded with desired status: "success"
+            [ check  ]   "lint" concluded with undesired status: "failure" ❌
+
+    -> checking PR #3
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" concluded with desired status: "success"
+            [ state  ]   "clean" is desirable
+            [ slot   ]   this is merge 2 of up to 3 for this repo
+            PR matches all criteria, I would've merged it if this weren't a dry run ✅
+    "#);
+}
+
+#[test]
+fn pr_with_an_updated_branch_is_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let behaviours = RunBehaviours {
+        execute: true,
+        ..RunBehaviours::default()
+    };
+    let mut l = RunLogger::new(&mut buffer, &behaviours);
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_branch_updated()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" concluded with desired status: "success"
+            PR's branch was behind its base; I've updated it, and will try merging it on a later run 🔄
+    "#);
+}
+
+#[test]
+fn prs_evaluated_by_rules_are_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![
+            merge_result_disqualified_after_author(
+                1,
+                Disqualification::Rule("no security fixes".to_string()),
+            ),
+            merge_result_disqualified_after_author(2, Disqualification::NoRuleMatched),
+            merge_result_qualified_by_rule(),
+        ]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ rule   ]   the PR is denied by the rule "no security fixes" ❌
+
+    -> checking PR #2
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ rule   ]   the PR doesn't match any of the allow rules ❌
+
+    -> checking PR #3
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" concluded with desired status: "success"
+            [ rule   ]   the PR is allowed by the rule "patch bumps for cargo"
+            [ state  ]   "clean" is desirable
+            PR matches all criteria, I would've merged it if this weren't a dry run ✅
+    "#);
+}
+
+#[test]
+fn pr_against_an_allowed_base_branch_is_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let behaviours = RunBehaviours {
+        execute: true,
+        ..RunBehaviours::default()
+    };
+    let mut l = RunLogger::new(&mut buffer, &behaviours);
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_qualified_against_base()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ base   ]   "release/1.x" matches one of the allowed base branches
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" concluded with desired status: "success"
+            [ state  ]   "clean" is desirable
+            PR merged! 🎉 ✅
+    "#);
+}
+
+#[test]
+fn updated_branches_are_included_in_summary() {
+    // GIVEN
+    let mut buffer = vec![];
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let summary = RunSummary {
+        disqualifications: vec![],
+        num_errors: 0,
+        prs_merged: vec![],
+        branches_updated: vec![MergedPR {
+            repo: format!("{OWNER}/{REPO}"),
+            title: PR_TITLE.to_string(),
+        }],
+        prs_enqueued: vec![],
+    };
+
+    // WHEN
+    l.write_output(&summary)
+        .expect("output should've been written");
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @"
+
+    ===========
+      SUMMARY
+    ===========
+
+    - PRs merged:                    0
+    - PRs disqualified:              0
+    - Errors encountered:            0
+
+    Branches updated
+    ---
+
+    - [dhth/mrj] build: bump clap from 4.5.39 to 4.5.40
+    ");
+}
+
+#[test]
+fn post_merge_actions_are_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let behaviours = RunBehaviours {
+        execute: true,
+        ..RunBehaviours::default()
+    };
+    let mut l = RunLogger::new(&mut buffer, &behaviours);
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_qualified_with_post_merge_actions()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ state  ]   "clean" is desirable
+            PR merged! 🎉 ✅
+            [ after  ]   deleted the PR's branch "dependabot/cargo/clap-4.5.40"
+            [ after  ]   closed PR #2, which this PR supersedes
+            [ after  ]   couldn't close PR #3, which this PR supersedes: couldn't close superseded PR: Not Found
+    "#);
+}
+
+#[test]
+fn post_merge_actions_are_printed_correctly_during_dry_runs() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![merge_result_qualified_with_post_merge_actions()]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ state  ]   "clean" is desirable
+            PR matches all criteria, I would've merged it if this weren't a dry run ✅
+            [ after  ]   would've deleted the PR's branch "dependabot/cargo/clap-4.5.40"
+            [ after  ]   would've closed PR #2, which this PR supersedes
+            [ after  ]   couldn't close PR #3, which this PR supersedes: couldn't close superseded PR: Not Found
+    "#);
+}
+
+#[test]
+fn enqueued_prs_are_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let behaviours = RunBehaviours {
+        execute: true,
+        ..RunBehaviours::default()
+    };
+    let mut l = RunLogger::new(&mut buffer, &behaviours);
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![
+            merge_result_enqueued(1, MergeStrategy::AutoMerge, false),
+            merge_result_enqueued(2, MergeStrategy::MergeQueue, false),
+        ]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" is pending; GitHub will wait for it before merging
+            [ state  ]   "blocked" is desirable
+            PR matches all criteria; I've enabled auto-merge, GitHub will merge it once it's ready 🚦 ✅
+
+    -> checking PR #2
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" is pending; GitHub will wait for it before merging
+            [ state  ]   "blocked" is desirable
+            PR matches all criteria; I've added it to the merge queue 🚦 ✅
+    "#);
+}
+
+#[test]
+fn enqueued_prs_are_printed_correctly_during_dry_runs() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![
+            merge_result_enqueued(1, MergeStrategy::AutoMerge, false),
+            merge_result_enqueued(2, MergeStrategy::MergeQueue, false),
+        ]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" is pending; GitHub will wait for it before merging
+            [ state  ]   "blocked" is desirable
+            PR matches all criteria, I would've enabled auto-merge if this weren't a dry run ✅
+
+    -> checking PR #2
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" is pending; GitHub will wait for it before merging
+            [ state  ]   "blocked" is desirable
+            PR matches all criteria, I would've added it to the merge queue if this weren't a dry run ✅
+    "#);
+}
+
+#[test]
+fn prs_already_handed_over_are_printed_correctly() {
+    // GIVEN
+    let mut buffer = vec![];
+
+    let behaviours = RunBehaviours {
+        execute: true,
+        ..RunBehaviours::default()
+    };
+    let mut l = RunLogger::new(&mut buffer, &behaviours);
+    let repo_check = RepoCheck {
+        owner: OWNER.to_string(),
+        name: REPO.to_string(),
+        state: RepoCheckFinished(vec![
+            merge_result_enqueued(1, MergeStrategy::AutoMerge, true),
+            merge_result_enqueued(2, MergeStrategy::MergeQueue, true),
+        ]),
+    };
+    let repo_result = RepoResult::Finished(repo_check);
+
+    // WHEN
+    l.add_repo_result(&repo_result);
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @r#"
+
+
+    =============
+      dhth/mrj
+    =============
+
+    -> checking PR #1
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" is pending; GitHub will wait for it before merging
+            [ state  ]   "blocked" is desirable
+            PR matches all criteria; auto-merge is already enabled, GitHub will merge it once it's ready 🚦 ✅
+
+    -> checking PR #2
+            build: bump clap from 4.5.39 to 4.5.40
+            https://github.com/dhth/mrj/pull/1
+            Created: Mon, 1 Jan 2024 01:01:01 +0000
+            Updated: Tue, 2 Jan 2024 01:01:01 +0000
+            [ head  ]   "dependabot/cargo/clap-4.5.40" matches the allowed head pattern
+            [ author ]   "dependabot[bot]" is in the list of trusted authors
+            [ check  ]   "test" is pending; GitHub will wait for it before merging
+            [ state  ]   "blocked" is desirable
+            PR matches all criteria; it's already in the merge queue 🚦 ✅
+    "#);
+}
+
+#[test]
+fn enqueued_prs_are_included_in_summary() {
+    // GIVEN
+    let mut buffer = vec![];
+    let mut l = RunLogger::new(&mut buffer, &RunBehaviours::default());
+    let summary = RunSummary {
+        disqualifications: vec![],
+        num_errors: 0,
+        prs_merged: vec![],
+        branches_updated: vec![],
+        prs_enqueued: vec![MergedPR {
+            repo: format!("{OWNER}/{REPO}"),
+            title: PR_TITLE.to_string(),
+        }],
+    };
+
+    // WHEN
+    l.write_output(&summary)
+        .expect("output should've been written");
+
+    // THEN
+    let out =
+        String::from_utf8(buffer).expect("buffer contents should've been converted to a string");
+    assert_snapshot!(out, @"
+
+    ===========
+      SUMMARY
+    ===========
+
+    - PRs merged:                    0
+    - PRs disqualified:              0
+    - Errors encountered:            0
+
+    PRs handed over to GitHub (auto-merge or merge queue)
+    ---
+
+    - [dhth/mrj] build: bump clap from 4.5.39 to 4.5.40
+    ");
+}
+
 #[test]
 fn pr_with_a_finished_check_is_printed_correctly() {
     // GIVEN
@@ -472,6 +1611,8 @@ fn summary_doesnt_include_dq_if_none_exist() {
         disqualifications: vec![],
         num_errors: 0,
         prs_merged: vec![],
+        branches_updated: vec![],
+        prs_enqueued: vec![],
     };
 
     l.write_output(&summary)
@@ -515,6 +1656,8 @@ fn disqualification_reasons_are_left_aligned_in_summary() {
         ],
         num_errors: 0,
         prs_merged: vec![],
+        branches_updated: vec![],
+        prs_enqueued: vec![],
     };
 
     l.write_output(&summary)
@@ -559,6 +1702,8 @@ fn merge_result_disqualified_unmatched_head(number: u64) -> MergeResult {
         url: format!("https://github.com/dhth/mrj/pull/{number}"),
         pr_created_at: Some(created_at()),
         pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
         qualifications: vec![],
         state: PRDisqualified(Disqualification::Head("improve tests".to_string())),
     })
@@ -571,6 +1716,8 @@ fn merge_result_disqualified_unknown_author() -> MergeResult {
         url: PR_URL.to_string(),
         pr_created_at: Some(created_at()),
         pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
         qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
         state: PRDisqualified(Disqualification::Author(None)),
     })
@@ -583,6 +1730,8 @@ fn merge_result_disqualified_untrusted_author() -> MergeResult {
         url: PR_URL.to_string(),
         pr_created_at: Some(created_at()),
         pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
         qualifications: vec![Qualification::Head(PR_HEAD.to_string())],
         state: PRDisqualified(Disqualification::Author(Some(
             "untrusted-dependabot[bot]".to_string(),
@@ -597,6 +1746,8 @@ fn merge_result_disqualified_check_with_unknown_conclusion() -> MergeResult {
         url: PR_URL.to_string(),
         pr_created_at: Some(created_at()),
         pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
         qualifications: vec![
             Qualification::Head(PR_HEAD.to_string()),
             Qualification::Author(PR_AUTHOR.to_string()),
@@ -627,6 +1778,8 @@ fn merge_result_disqualified_failed_check() -> MergeResult {
         url: PR_URL.to_string(),
         pr_created_at: Some(created_at()),
         pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
         qualifications: vec![
             Qualification::Head(PR_HEAD.to_string()),
             Qualification::Author(PR_AUTHOR.to_string()),
@@ -657,6 +1810,8 @@ fn merge_result_disqualified_unknown_state() -> MergeResult {
         url: PR_URL.to_string(),
         pr_created_at: Some(created_at()),
         pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
         qualifications: vec![
             Qualification::Head(PR_HEAD.to_string()),
             Qualification::Author(PR_AUTHOR.to_string()),
@@ -684,6 +1839,8 @@ fn merge_result_disqualified_dirty_state() -> MergeResult {
         url: PR_URL.to_string(),
         pr_created_at: Some(created_at()),
         pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
         qualifications: vec![
             Qualification::Head(PR_HEAD.to_string()),
             Qualification::Author(PR_AUTHOR.to_string()),
@@ -704,6 +1861,184 @@ fn merge_result_disqualified_dirty_state() -> MergeResult {
     })
 }
 
+fn merge_result_disqualified_major_update() -> MergeResult {
+    MergeResult::Disqualified(PRCheck {
+        number: 1,
+        title: "build: bump clap from 4.5.40 to 5.0.0".to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head("dependabot/cargo/clap-5.0.0".to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+        ],
+        state: PRDisqualified(Disqualification::UpdateType {
+            package: Some("clap".to_string()),
+            update_type: Some(UpdateType::Major),
+        }),
+    })
+}
+
+fn merge_result_disqualified_denied_package() -> MergeResult {
+    MergeResult::Disqualified(PRCheck {
+        number: 1,
+        title: "build: bump openssl from 0.10.72 to 0.10.73".to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head("dependabot/cargo/openssl-0.10.73".to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+        ],
+        state: PRDisqualified(Disqualification::PackageDenied("openssl".to_string())),
+    })
+}
+
+fn merge_result_disqualified_ecosystem() -> MergeResult {
+    MergeResult::Disqualified(PRCheck {
+        number: 1,
+        title: "build(deps): bump lodash from 4.17.20 to 4.17.21".to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: Some("npm_and_yarn".to_string()),
+        qualifications: vec![
+            Qualification::Head("dependabot/npm_and_yarn/lodash-4.17.21".to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+        ],
+        state: PRDisqualified(Disqualification::Ecosystem(Some(
+            "npm_and_yarn".to_string(),
+        ))),
+    })
+}
+
+fn merge_result_disqualified_missing_required_check() -> MergeResult {
+    MergeResult::Disqualified(PRCheck {
+        number: 1,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+        ],
+        state: PRDisqualified(Disqualification::RequiredCheckMissing("test".to_string())),
+    })
+}
+
+fn merge_result_disqualified_frozen() -> MergeResult {
+    MergeResult::Disqualified(PRCheck {
+        number: 1,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+        ],
+        state: PRDisqualified(Disqualification::Frozen {
+            label: "merge-freeze".to_string(),
+            pr: 12,
+        }),
+    })
+}
+
+fn merge_result_disqualified_missing_approval() -> MergeResult {
+    MergeResult::Disqualified(PRCheck {
+        number: 1,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+            Qualification::Check {
+                name: "test".to_string(),
+                conclusion: "success".to_string(),
+            },
+        ],
+        state: PRDisqualified(Disqualification::ApprovalMissing),
+    })
+}
+
+fn merge_result_disqualified_cooling_down() -> MergeResult {
+    MergeResult::Disqualified(PRCheck {
+        number: 1,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+        ],
+        state: PRDisqualified(Disqualification::CoolingDown {
+            eligible_at: Utc.with_ymd_and_hms(2024, 1, 4, 1, 1, 1).unwrap(),
+            remaining: HumanDuration::from(std::time::Duration::from_secs(41 * 60 * 60 + 30)),
+        }),
+    })
+}
+
+fn merge_result_disqualified_after_author(number: u64, dq: Disqualification) -> MergeResult {
+    MergeResult::Disqualified(PRCheck {
+        number,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+        ],
+        state: PRDisqualified(dq),
+    })
+}
+
+fn merge_result_qualified_with_auto_approval() -> MergeResult {
+    MergeResult::Qualified(PRCheck {
+        number: 2,
+        title: "build: bump serde from 1.0.227 to 1.0.228".to_string(),
+        url: "https://github.com/dhth/mrj/pull/2".to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head("dependabot/cargo/serde-1.0.228".to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+            Qualification::Check {
+                name: "test".to_string(),
+                conclusion: "success".to_string(),
+            },
+            Qualification::State("blocked".to_string()),
+            Qualification::AutoApproval,
+        ],
+        state: PRCheckFinished {
+            head_sha: PR_SHA.to_string(),
+            merge_commit_sha: None,
+            post_merge: vec![],
+        },
+    })
+}
+
 fn merge_result_qualified() -> MergeResult {
     MergeResult::Qualified(PRCheck {
         number: 1,
@@ -711,6 +2046,8 @@ fn merge_result_qualified() -> MergeResult {
         url: PR_URL.to_string(),
         pr_created_at: Some(created_at()),
         pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
         qualifications: vec![
             Qualification::Head(PR_HEAD.to_string()),
             Qualification::Author(PR_AUTHOR.to_string()),
@@ -728,7 +2065,204 @@ fn merge_result_qualified() -> MergeResult {
             },
             Qualification::State("clean".to_string()),
         ],
-        state: PRCheckFinished,
+        state: PRCheckFinished {
+            head_sha: PR_SHA.to_string(),
+            merge_commit_sha: None,
+            post_merge: vec![],
+        },
+    })
+}
+
+fn merge_result_qualified_by_rule() -> MergeResult {
+    MergeResult::Qualified(PRCheck {
+        number: 3,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+            Qualification::Check {
+                name: "test".to_string(),
+                conclusion: "success".to_string(),
+            },
+            Qualification::Rule("patch bumps for cargo".to_string()),
+            Qualification::State("clean".to_string()),
+        ],
+        state: PRCheckFinished {
+            head_sha: PR_SHA.to_string(),
+            merge_commit_sha: None,
+            post_merge: vec![],
+        },
+    })
+}
+
+fn merge_result_qualified_against_base() -> MergeResult {
+    MergeResult::Qualified(PRCheck {
+        number: 1,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Base("release/1.x".to_string()),
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+            Qualification::Check {
+                name: "test".to_string(),
+                conclusion: "success".to_string(),
+            },
+            Qualification::State("clean".to_string()),
+        ],
+        state: PRCheckFinished {
+            head_sha: PR_SHA.to_string(),
+            merge_commit_sha: None,
+            post_merge: vec![],
+        },
+    })
+}
+
+fn merge_result_branch_updated() -> MergeResult {
+    MergeResult::BranchUpdated(PRCheck {
+        number: 1,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+            Qualification::Check {
+                name: "test".to_string(),
+                conclusion: "success".to_string(),
+            },
+        ],
+        state: PRBranchUpdated,
+    })
+}
+
+fn merge_result_qualified_with_post_merge_actions() -> MergeResult {
+    let mut pr_check = PRCheck {
+        number: 1,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+            Qualification::State("clean".to_string()),
+        ],
+        state: PRCheckFinished {
+            head_sha: PR_SHA.to_string(),
+            merge_commit_sha: None,
+            post_merge: vec![],
+        },
+    };
+    pr_check.add_post_merge_outcome(PostMergeAction::DeleteBranch(PR_HEAD.to_string()), None);
+    pr_check.add_post_merge_outcome(PostMergeAction::CloseSuperseded(2), None);
+    pr_check.add_post_merge_outcome(
+        PostMergeAction::CloseSuperseded(3),
+        Some("couldn't close superseded PR: Not Found".to_string()),
+    );
+
+    MergeResult::Qualified(pr_check)
+}
+
+fn merge_result_enqueued(
+    number: u64,
+    strategy: MergeStrategy,
+    already_handed_over: bool,
+) -> MergeResult {
+    MergeResult::Enqueued(PRCheck {
+        number,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+            Qualification::PendingCheck("test".to_string()),
+            Qualification::State("blocked".to_string()),
+        ],
+        state: PREnqueued {
+            strategy,
+            head_sha: PR_SHA.to_string(),
+            already_handed_over,
+        },
+    })
+}
+
+fn merge_result_qualified_with_ignored_check() -> MergeResult {
+    MergeResult::Qualified(PRCheck {
+        number: 1,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+            Qualification::IgnoredCheck {
+                name: "codecov/patch".to_string(),
+                conclusion: Some("failure".to_string()),
+            },
+            Qualification::IgnoredCheck {
+                name: "preview-docs".to_string(),
+                conclusion: None,
+            },
+            Qualification::Check {
+                name: "test".to_string(),
+                conclusion: "success".to_string(),
+            },
+            Qualification::State("clean".to_string()),
+        ],
+        state: PRCheckFinished {
+            head_sha: PR_SHA.to_string(),
+            merge_commit_sha: None,
+            post_merge: vec![],
+        },
+    })
+}
+
+fn merge_result_qualified_in_slot(number: u64, slot: usize) -> MergeResult {
+    MergeResult::Qualified(PRCheck {
+        number,
+        title: PR_TITLE.to_string(),
+        url: PR_URL.to_string(),
+        pr_created_at: Some(created_at()),
+        pr_updated_at: Some(updated_at()),
+        upgrade: None,
+        ecosystem: None,
+        qualifications: vec![
+            Qualification::Head(PR_HEAD.to_string()),
+            Qualification::Author(PR_AUTHOR.to_string()),
+            Qualification::Check {
+                name: "test".to_string(),
+                conclusion: "success".to_string(),
+            },
+            Qualification::State("clean".to_string()),
+            Qualification::MergeSlot { slot, max: 3 },
+        ],
+        state: PRCheckFinished {
+            head_sha: PR_SHA.to_string(),
+            merge_commit_sha: None,
+            post_merge: vec![],
+        },
     })
 }
 
@@ -745,6 +2279,8 @@ fn summary_with_disqualifications() -> RunSummary {
         ],
         num_errors: 0,
         prs_merged: vec![],
+        branches_updated: vec![],
+        prs_enqueued: vec![],
     }
 }
 
@@ -1 +1,14 @@
 mod log;
+
+use crate::config::Config;
+
+/// A config with only the mandatory settings, and `extra` on top of them.
+pub(super) fn config(extra: &str) -> Config {
+    toml::from_str(&format!(
+        r#"trusted_authors = ["dependabot[bot]"]
+merge_type = "squash"
+{extra}
+"#
+    ))
+    .expect("config should've been parsed")
+}
@@ -1,13 +1,16 @@
 use super::schema::{
-    StoredDisqualification, StoredPrRecord, StoredPrStatus, StoredQualification, StoredRepoRecord,
-    StoredRepoStatus, StoredRunConfig, StoredRunData, StoredRunEnvelope, StoredRunFlags,
-    StoredRunMode, StoredRunSummary,
+    StoredDisqualification, StoredPostMergeAction, StoredPrRecord, StoredPrStatus,
+    StoredQualification, StoredRepoRecord, StoredRepoSettings, StoredRepoStatus, StoredRule,
+    StoredRunConfig, StoredRunData, StoredRunEnvelope, StoredRunFlags, StoredRunMode,
+    StoredRunSummary, StoredUpgrade, StoredWaitForPendingChecks,
 };
-use crate::config::Config;
+use crate::config::{Config, RepoSettings};
 use crate::domain::{
-    Disqualification, MergeResult, Qualification, RepoResult, RunMergeResults, RunSummary,
+    Disqualification, MergeResult, MergeStrategy, PostMergeAction, PostMergeOutcome, Qualification,
+    Repo, RepoResult, RunMergeResults, RunSummary,
 };
 use crate::merge::RunBehaviours;
+use crate::upgrade::Upgrade;
 use anyhow::Context;
 use std::fs::OpenOptions;
 use std::io::Write;
@@ -22,6 +25,7 @@ pub fn persist_run(
     output_path: &Path,
 ) -> anyhow::Result<()> {
     let RunMergeResults {
+        selected_repos,
         results,
         summary,
         started_at,
@@ -41,9 +45,10 @@ pub fn persist_run(
             },
             config: map_config(config, behaviours),
             summary: map_summary(summary),
+            selected_repos: selected_repos.iter().map(|r| r.to_string()).collect(),
             repos: results
                 .into_iter()
-                .map(|result| map_repo_result(result, behaviours.execute))
+                .map(|result| map_repo_result(result, config, behaviours.execute))
                 .collect(),
         },
     };
@@ -63,6 +68,8 @@ fn map_summary(summary: RunSummary) -> StoredRunSummary {
         num_disqualifications: summary.disqualifications.len(),
         num_errors: summary.num_errors,
         num_merged: summary.prs_merged.len(),
+        num_branches_updated: summary.branches_updated.len(),
+        num_enqueued: summary.prs_enqueued.len(),
     }
 }
 
@@ -76,6 +83,10 @@ fn map_config(config: &Config, behaviours: &RunBehaviours) -> StoredRunConfig {
         merge_if_blocked: config.merge_if_blocked,
         merge_if_checks_skipped: config.merge_if_checks_skipped,
         merge_if_checks_neutral: config.merge_if_checks_neutral,
+        allowed_update_types: config
+            .allowed_update_types
+            .as_ref()
+            .map(|types| types.iter().map(|t| t.into()).collect()),
         merge_type: (&config.merge_type).into(),
         sort_by: (&config.sort_by).into(),
         sort_direction: (&config.sort_direction).into(),
@@ -84,14 +95,112 @@ fn map_config(config: &Config, behaviours: &RunBehaviours) -> StoredRunConfig {
             show_prs_from_untrusted_authors: behaviours.show_prs_from_untrusted_authors,
             show_prs_with_unmatched_head: behaviours.show_prs_with_unmatched_head,
             skip_disqualifications_in_summary: behaviours.skip_disqualifications_in_summary,
+            show_draft_prs: behaviours.show_draft_prs,
+            show_stale_prs: behaviours.show_stale_prs,
         },
     }
 }
 
-fn map_repo_result(result: RepoResult, did_execute: bool) -> StoredRepoRecord {
+fn map_repo_settings(settings: RepoSettings) -> StoredRepoSettings {
+    StoredRepoSettings {
+        trusted_authors: settings.trusted_authors,
+        base_branch: settings.base_branch,
+        base_branches: settings
+            .base_branches
+            .iter()
+            .map(|g| g.to_string())
+            .collect(),
+        head_pattern: settings
+            .head_pattern
+            .map(|pattern| pattern.re.as_str().to_string()),
+        merge_if_blocked: settings.merge_if_blocked,
+        update_behind_branches: settings.update_behind_branches,
+        request_rebases: settings.request_rebases,
+        merge_if_checks_skipped: settings.merge_if_checks_skipped,
+        merge_if_checks_neutral: settings.merge_if_checks_neutral,
+        required_checks: settings
+            .required_checks
+            .iter()
+            .map(|c| c.to_string())
+            .collect(),
+        min_checks: (settings.min_checks > 0).then_some(settings.min_checks),
+        ignored_checks: settings
+            .ignored_checks
+            .iter()
+            .map(|c| c.to_string())
+            .collect(),
+        wait_for_pending_checks: settings.wait_for_pending_checks.map(|w| {
+            StoredWaitForPendingChecks {
+                timeout: w.timeout.to_string(),
+                poll_interval: w.poll_interval.to_string(),
+            }
+        }),
+        required_labels: settings.required_labels,
+        blocking_labels: settings.blocking_labels,
+        freeze_label: settings.freeze_label,
+        require_approval: settings.require_approval,
+        trusted_reviewers: settings.trusted_reviewers,
+        auto_approve: settings.auto_approve,
+        min_pr_age: settings.min_pr_age.map(|age| age.to_string()),
+        skip_drafts: settings.skip_drafts,
+        max_pr_age: settings.max_pr_age.map(|age| age.to_string()),
+        allowed_update_types: settings
+            .allowed_update_types
+            .map(|types| types.iter().map(|t| t.into()).collect()),
+        allow_packages: settings
+            .allow_packages
+            .iter()
+            .map(|g| g.to_string())
+            .collect(),
+        deny_packages: settings
+            .deny_packages
+            .iter()
+            .map(|g| g.to_string())
+            .collect(),
+        deny_versions: settings
+            .deny_versions
+            .iter()
+            .map(|pv| pv.to_string())
+            .collect(),
+        ecosystems: settings.ecosystems,
+        allowed_paths: settings
+            .allowed_paths
+            .iter()
+            .map(|g| g.to_string())
+            .collect(),
+        max_changed_files: settings.max_changed_files,
+        max_additions: settings.max_additions,
+        rules: settings
+            .rules
+            .iter()
+            .map(|r| StoredRule {
+                name: r.name.clone(),
+                rule: r.rule.to_string(),
+            })
+            .collect(),
+        merge_type: (&settings.merge_type).into(),
+        merge_strategy: settings.merge_strategy.into(),
+        commit_title_template: settings.commit_title_template.map(|t| t.to_string()),
+        commit_message_template: settings.commit_message_template.map(|t| t.to_string()),
+        max_merges_per_repo: settings.max_merges_per_repo,
+        merge_delay: settings.merge_delay.map(|delay| delay.to_string()),
+        delete_branch_after_merge: settings.delete_branch_after_merge,
+        close_superseded: settings.close_superseded,
+    }
+}
+
+fn map_repo_result(result: RepoResult, config: &Config, did_execute: bool) -> StoredRepoRecord {
+    let settings = |owner: &str, name: &str| {
+        map_repo_settings(config.settings_for(&Repo {
+            owner: owner.to_string(),
+            repo: name.to_string(),
+        }))
+    };
+
     match result {
         RepoResult::Errored(repo_check) => StoredRepoRecord {
             repo: format!("{}/{}", repo_check.owner, repo_check.name),
+            settings: Some(settings(&repo_check.owner, &repo_check.name)),
             owner: repo_check.owner,
             name: repo_check.name,
             status: StoredRepoStatus::Errored,
@@ -102,6 +211,7 @@ fn map_repo_result(result: RepoResult, did_execute: bool) -> StoredRepoRecord {
             let repo = format!("{}/{}", repo_check.owner, repo_check.name);
             StoredRepoRecord {
                 repo,
+                settings: Some(settings(&repo_check.owner, &repo_check.name)),
                 owner: repo_check.owner,
                 name: repo_check.name,
                 status: StoredRepoStatus::Finished,
@@ -125,6 +235,8 @@ fn map_pr_result(result: MergeResult, did_execute: bool) -> StoredPrRecord {
             url: pr_check.url,
             created_at: pr_check.pr_created_at,
             updated_at: pr_check.pr_updated_at,
+            upgrade: pr_check.upgrade.map(map_upgrade),
+            ecosystem: pr_check.ecosystem,
             status: StoredPrStatus::Qualified,
             qualifications: pr_check
                 .qualifications
@@ -134,6 +246,59 @@ fn map_pr_result(result: MergeResult, did_execute: bool) -> StoredPrRecord {
             disqualification: None,
             error: None,
             merged: did_execute,
+            head_sha: Some(pr_check.state.head_sha),
+            merge_commit_sha: pr_check.state.merge_commit_sha,
+            post_merge_actions: pr_check
+                .state
+                .post_merge
+                .into_iter()
+                .map(map_post_merge_outcome)
+                .collect(),
+        },
+        MergeResult::BranchUpdated(pr_check) => StoredPrRecord {
+            number: pr_check.number,
+            title: pr_check.title,
+            url: pr_check.url,
+            created_at: pr_check.pr_created_at,
+            updated_at: pr_check.pr_updated_at,
+            upgrade: pr_check.upgrade.map(map_upgrade),
+            ecosystem: pr_check.ecosystem,
+            status: StoredPrStatus::BranchUpdated,
+            qualifications: pr_check
+                .qualifications
+                .into_iter()
+                .map(map_qualification)
+                .collect(),
+            disqualification: None,
+            error: None,
+            merged: false,
+            head_sha: None,
+            merge_commit_sha: None,
+            post_merge_actions: vec![],
+        },
+        MergeResult::Enqueued(pr_check) => StoredPrRecord {
+            number: pr_check.number,
+            title: pr_check.title,
+            url: pr_check.url,
+            created_at: pr_check.pr_created_at,
+            updated_at: pr_check.pr_updated_at,
+            upgrade: pr_check.upgrade.map(map_upgrade),
+            ecosystem: pr_check.ecosystem,
+            status: match pr_check.state.strategy {
+                MergeStrategy::MergeQueue => StoredPrStatus::Enqueued,
+                _ => StoredPrStatus::AutoMergeEnabled,
+            },
+            qualifications: pr_check
+                .qualifications
+                .into_iter()
+                .map(map_qualification)
+                .collect(),
+            disqualification: None,
+            error: None,
+            merged: false,
+            head_sha: Some(pr_check.state.head_sha),
+            merge_commit_sha: None,
+            post_merge_actions: vec![],
         },
         MergeResult::Disqualified(pr_check) => StoredPrRecord {
             number: pr_check.number,
@@ -141,6 +306,8 @@ fn map_pr_result(result: MergeResult, did_execute: bool) -> StoredPrRecord {
             url: pr_check.url,
             created_at: pr_check.pr_created_at,
             updated_at: pr_check.pr_updated_at,
+            upgrade: pr_check.upgrade.map(map_upgrade),
+            ecosystem: pr_check.ecosystem,
             status: StoredPrStatus::Disqualified,
             qualifications: pr_check
                 .qualifications
@@ -150,6 +317,9 @@ fn map_pr_result(result: MergeResult, did_execute: bool) -> StoredPrRecord {
             disqualification: Some(map_disqualification(pr_check.state.0)),
             error: None,
             merged: false,
+            head_sha: None,
+            merge_commit_sha: None,
+            post_merge_actions: vec![],
         },
         MergeResult::Errored(pr_check) => StoredPrRecord {
             number: pr_check.number,
@@ -157,6 +327,8 @@ fn map_pr_result(result: MergeResult, did_execute: bool) -> StoredPrRecord {
             url: pr_check.url,
             created_at: pr_check.pr_created_at,
             updated_at: pr_check.pr_updated_at,
+            upgrade: pr_check.upgrade.map(map_upgrade),
+            ecosystem: pr_check.ecosystem,
             status: StoredPrStatus::Errored,
             qualifications: pr_check
                 .qualifications
@@ -166,18 +338,64 @@ fn map_pr_result(result: MergeResult, did_execute: bool) -> StoredPrRecord {
             disqualification: None,
             error: Some(format!("{:#}", pr_check.state.reason())),
             merged: false,
+            head_sha: None,
+            merge_commit_sha: None,
+            post_merge_actions: vec![],
         },
     }
 }
 
+fn map_post_merge_outcome(outcome: PostMergeOutcome) -> StoredPostMergeAction {
+    match outcome.action {
+        PostMergeAction::DeleteBranch(branch) => StoredPostMergeAction::DeleteBranch {
+            branch,
+            error: outcome.error,
+        },
+        PostMergeAction::CloseSuperseded(pr) => StoredPostMergeAction::CloseSuperseded {
+            pr,
+            error: outcome.error,
+        },
+    }
+}
+
+fn map_upgrade(upgrade: Upgrade) -> StoredUpgrade {
+    StoredUpgrade {
+        update_type: upgrade.update_type().as_ref().map(|u| u.into()),
+        package: upgrade.package,
+        from: upgrade.from.map(|v| v.to_string()),
+        to: upgrade.to.to_string(),
+        ecosystem: upgrade.ecosystem,
+    }
+}
+
 fn map_qualification(qualification: Qualification) -> StoredQualification {
     match qualification {
+        Qualification::Base(value) => StoredQualification::Base { value },
         Qualification::Head(value) => StoredQualification::Head { value },
         Qualification::Author(value) => StoredQualification::Author { value },
         Qualification::Check { name, conclusion } => {
             StoredQualification::Check { name, conclusion }
         }
         Qualification::State(value) => StoredQualification::State { value },
+        Qualification::UpdateType {
+            package,
+            update_type,
+        } => StoredQualification::UpdateType {
+            package,
+            update_type: (&update_type).into(),
+        },
+        Qualification::Package(value) => StoredQualification::Package { value },
+        Qualification::Ecosystem(value) => StoredQualification::Ecosystem { value },
+        Qualification::IgnoredCheck { name, conclusion } => {
+            StoredQualification::IgnoredCheck { name, conclusion }
+        }
+        Qualification::Label(value) => StoredQualification::Label { value },
+        Qualification::Approval(value) => StoredQualification::Approval { value },
+        Qualification::AutoApproval => StoredQualification::AutoApproval,
+        Qualification::MergeSlot { slot, max } => StoredQualification::MergeSlot { slot, max },
+        Qualification::AllowedPaths(num_files) => StoredQualification::AllowedPaths { num_files },
+        Qualification::PendingCheck(name) => StoredQualification::PendingCheck { name },
+        Qualification::Rule(value) => StoredQualification::Rule { value },
     }
 }
 
@@ -189,6 +407,65 @@ fn map_disqualification(disqualification: Disqualification) -> StoredDisqualific
             StoredDisqualification::Check { name, conclusion }
         }
         Disqualification::State(value) => StoredDisqualification::State { value },
+        Disqualification::UpdateType {
+            package,
+            update_type,
+        } => StoredDisqualification::UpdateType {
+            package,
+            update_type: update_type.as_ref().map(|u| u.into()),
+        },
+        Disqualification::PackageNotAllowed(value) => {
+            StoredDisqualification::PackageNotAllowed { value }
+        }
+        Disqualification::PackageDenied(value) => StoredDisqualification::PackageDenied { value },
+        Disqualification::VersionDenied { package, version } => {
+            StoredDisqualification::VersionDenied { package, version }
+        }
+        Disqualification::Ecosystem(value) => StoredDisqualification::Ecosystem { value },
+        Disqualification::RequiredCheckMissing(value) => {
+            StoredDisqualification::RequiredCheckMissing { value }
+        }
+        Disqualification::TooFewChecks { found, required } => {
+            StoredDisqualification::TooFewChecks { found, required }
+        }
+        Disqualification::RequiredLabelMissing(value) => {
+            StoredDisqualification::RequiredLabelMissing { value }
+        }
+        Disqualification::BlockingLabel(value) => StoredDisqualification::BlockingLabel { value },
+        Disqualification::Frozen { label, pr } => StoredDisqualification::Frozen { label, pr },
+        Disqualification::ApprovalMissing => StoredDisqualification::ApprovalMissing,
+        Disqualification::CoolingDown {
+            eligible_at,
+            remaining,
+        } => StoredDisqualification::CoolingDown {
+            eligible_at,
+            remaining: remaining.to_string(),
+        },
+        Disqualification::Draft => StoredDisqualification::Draft,
+        Disqualification::Stale { max_age } => StoredDisqualification::Stale {
+            max_age: max_age.to_string(),
+        },
+        Disqualification::RebaseRequested(bot) => StoredDisqualification::RebaseRequested {
+            bot: bot.to_string(),
+        },
+        Disqualification::RebasePending { bot, requested_at } => {
+            StoredDisqualification::RebasePending {
+                bot: bot.to_string(),
+                requested_at,
+            }
+        }
+        Disqualification::HeadChanged => StoredDisqualification::HeadChanged,
+        Disqualification::PathsNotAllowed(paths) => {
+            StoredDisqualification::PathsNotAllowed { paths }
+        }
+        Disqualification::TooManyChangedFiles { found, max } => {
+            StoredDisqualification::TooManyChangedFiles { found, max }
+        }
+        Disqualification::TooManyAdditions { found, max } => {
+            StoredDisqualification::TooManyAdditions { found, max }
+        }
+        Disqualification::Rule(value) => StoredDisqualification::Rule { value },
+        Disqualification::NoRuleMatched => StoredDisqualification::NoRuleMatched,
     }
 }
 
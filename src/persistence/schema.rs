@@ -1,4 +1,5 @@
-use crate::domain::{MergeType, SortBy, SortDirection};
+use crate::domain::{MergeStrategy, MergeType, SortBy, SortDirection};
+use crate::upgrade::UpdateType;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +17,8 @@ pub struct StoredRunData {
     pub mode: StoredRunMode,
     pub config: StoredRunConfig,
     pub summary: StoredRunSummary,
+    #[serde(default)]
+    pub selected_repos: Vec<String>,
     pub repos: Vec<StoredRepoRecord>,
 }
 
@@ -24,6 +27,10 @@ pub struct StoredRunSummary {
     pub num_disqualifications: usize,
     pub num_errors: u16,
     pub num_merged: usize,
+    #[serde(default)]
+    pub num_branches_updated: usize,
+    #[serde(default)]
+    pub num_enqueued: usize,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -43,6 +50,8 @@ pub struct StoredRunConfig {
     pub merge_if_checks_skipped: bool,
     #[serde(default)]
     pub merge_if_checks_neutral: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_update_types: Option<Vec<StoredUpdateType>>,
     pub merge_type: StoredMergeType,
     pub sort_by: StoredSortBy,
     pub sort_direction: StoredSortDirection,
@@ -57,6 +66,24 @@ pub enum StoredMergeType {
     Rebase,
 }
 
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StoredMergeStrategy {
+    #[default]
+    Direct,
+    AutoMerge,
+    MergeQueue,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StoredUpdateType {
+    Major,
+    Minor,
+    Patch,
+    Prerelease,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum StoredSortBy {
@@ -83,6 +110,27 @@ impl From<&MergeType> for StoredMergeType {
     }
 }
 
+impl From<MergeStrategy> for StoredMergeStrategy {
+    fn from(value: MergeStrategy) -> Self {
+        match value {
+            MergeStrategy::Direct => StoredMergeStrategy::Direct,
+            MergeStrategy::AutoMerge => StoredMergeStrategy::AutoMerge,
+            MergeStrategy::MergeQueue => StoredMergeStrategy::MergeQueue,
+        }
+    }
+}
+
+impl From<&UpdateType> for StoredUpdateType {
+    fn from(value: &UpdateType) -> Self {
+        match value {
+            UpdateType::Major => StoredUpdateType::Major,
+            UpdateType::Minor => StoredUpdateType::Minor,
+            UpdateType::Patch => StoredUpdateType::Patch,
+            UpdateType::Prerelease => StoredUpdateType::Prerelease,
+        }
+    }
+}
+
 impl From<&SortBy> for StoredSortBy {
     fn from(value: &SortBy) -> Self {
         match value {
@@ -109,6 +157,10 @@ pub struct StoredRunFlags {
     pub show_prs_from_untrusted_authors: bool,
     pub show_prs_with_unmatched_head: bool,
     pub skip_disqualifications_in_summary: bool,
+    #[serde(default)]
+    pub show_draft_prs: bool,
+    #[serde(default)]
+    pub show_stale_prs: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -117,11 +169,102 @@ pub struct StoredRepoRecord {
     pub owner: String,
     pub name: String,
     pub status: StoredRepoStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<StoredRepoSettings>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
     pub prs: Vec<StoredPrRecord>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StoredRepoSettings {
+    pub trusted_authors: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub base_branches: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head_pattern: Option<String>,
+    pub merge_if_blocked: bool,
+    #[serde(default)]
+    pub update_behind_branches: bool,
+    #[serde(default)]
+    pub request_rebases: bool,
+    pub merge_if_checks_skipped: bool,
+    pub merge_if_checks_neutral: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_checks: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_checks: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignored_checks: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for_pending_checks: Option<StoredWaitForPendingChecks>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocking_labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freeze_label: Option<String>,
+    #[serde(default)]
+    pub require_approval: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_reviewers: Vec<String>,
+    #[serde(default)]
+    pub auto_approve: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_pr_age: Option<String>,
+    #[serde(default = "default_true")]
+    pub skip_drafts: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pr_age: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_update_types: Option<Vec<StoredUpdateType>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_packages: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny_packages: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny_versions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ecosystems: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_paths: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_changed_files: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_additions: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<StoredRule>,
+    pub merge_type: StoredMergeType,
+    #[serde(default)]
+    pub merge_strategy: StoredMergeStrategy,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_title_template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_message_template: Option<String>,
+    #[serde(default = "default_one")]
+    pub max_merges_per_repo: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_delay: Option<String>,
+    #[serde(default)]
+    pub delete_branch_after_merge: bool,
+    #[serde(default)]
+    pub close_superseded: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StoredWaitForPendingChecks {
+    pub timeout: String,
+    pub poll_interval: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StoredRule {
+    pub name: String,
+    pub rule: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum StoredRepoStatus {
@@ -138,6 +281,10 @@ pub struct StoredPrRecord {
     pub created_at: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upgrade: Option<StoredUpgrade>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ecosystem: Option<String>,
     pub status: StoredPrStatus,
     pub qualifications: Vec<StoredQualification>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -145,12 +292,48 @@ pub struct StoredPrRecord {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
     pub merged: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head_sha: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_commit_sha: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_merge_actions: Vec<StoredPostMergeAction>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum StoredPostMergeAction {
+    DeleteBranch {
+        branch: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    CloseSuperseded {
+        pr: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StoredUpgrade {
+    pub package: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    pub to: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ecosystem: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_type: Option<StoredUpdateType>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum StoredPrStatus {
     Qualified,
+    BranchUpdated,
+    AutoMergeEnabled,
+    Enqueued,
     Disqualified,
     Errored,
 }
@@ -158,10 +341,56 @@ pub enum StoredPrStatus {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "kind", rename_all = "kebab-case")]
 pub enum StoredQualification {
-    Head { value: String },
-    Author { value: String },
-    Check { name: String, conclusion: String },
-    State { value: String },
+    Base {
+        value: String,
+    },
+    Head {
+        value: String,
+    },
+    Author {
+        value: String,
+    },
+    Check {
+        name: String,
+        conclusion: String,
+    },
+    State {
+        value: String,
+    },
+    UpdateType {
+        package: String,
+        update_type: StoredUpdateType,
+    },
+    Package {
+        value: String,
+    },
+    Ecosystem {
+        value: String,
+    },
+    IgnoredCheck {
+        name: String,
+        conclusion: Option<String>,
+    },
+    Label {
+        value: String,
+    },
+    Approval {
+        value: String,
+    },
+    AutoApproval,
+    MergeSlot {
+        slot: usize,
+        max: usize,
+    },
+    AllowedPaths {
+        num_files: usize,
+    },
+    PendingCheck {
+        name: String,
+    },
+    Rule {
+        value: String,
+    },
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -180,6 +409,81 @@ pub enum StoredDisqualification {
     State {
         value: Option<String>,
     },
+    UpdateType {
+        package: Option<String>,
+        update_type: Option<StoredUpdateType>,
+    },
+    PackageNotAllowed {
+        value: Option<String>,
+    },
+    PackageDenied {
+        value: String,
+    },
+    VersionDenied {
+        package: String,
+        version: String,
+    },
+    Ecosystem {
+        value: Option<String>,
+    },
+    RequiredCheckMissing {
+        value: String,
+    },
+    TooFewChecks {
+        found: usize,
+        required: usize,
+    },
+    RequiredLabelMissing {
+        value: String,
+    },
+    BlockingLabel {
+        value: String,
+    },
+    Frozen {
+        label: String,
+        pr: u64,
+    },
+    ApprovalMissing,
+    CoolingDown {
+        eligible_at: DateTime<Utc>,
+        remaining: String,
+    },
+    Draft,
+    Stale {
+        max_age: String,
+    },
+    RebaseRequested {
+        bot: String,
+    },
+    RebasePending {
+        bot: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        requested_at: Option<DateTime<Utc>>,
+    },
+    HeadChanged,
+    PathsNotAllowed {
+        paths: Vec<String>,
+    },
+    TooManyChangedFiles {
+        found: u64,
+        max: u64,
+    },
+    TooManyAdditions {
+        found: u64,
+        max: u64,
+    },
+    Rule {
+        value: String,
+    },
+    NoRuleMatched,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_one() -> usize {
+    1
 }
 
 #[cfg(test)]
@@ -48,9 +48,10 @@ pub(super) fn render_report(
 mod tests {
     use super::*;
     use crate::persistence::schema::{
-        StoredDisqualification, StoredMergeType, StoredPrRecord, StoredPrStatus,
-        StoredQualification, StoredRepoRecord, StoredRepoStatus, StoredRunConfig, StoredRunData,
-        StoredRunFlags, StoredRunMode, StoredRunSummary, StoredSortBy, StoredSortDirection,
+        StoredDisqualification, StoredMergeType, StoredPostMergeAction, StoredPrRecord,
+        StoredPrStatus, StoredQualification, StoredRepoRecord, StoredRepoStatus, StoredRunConfig,
+        StoredRunData, StoredRunFlags, StoredRunMode, StoredRunSummary, StoredSortBy,
+        StoredSortDirection, StoredUpdateType, StoredUpgrade,
     };
     use chrono::TimeZone;
     use insta::assert_snapshot;
@@ -117,6 +118,10 @@ mod tests {
                     merge_if_blocked: false,
                     merge_if_checks_skipped: true,
                     merge_if_checks_neutral: false,
+                    allowed_update_types: Some(vec![
+                        StoredUpdateType::Patch,
+                        StoredUpdateType::Minor,
+                    ]),
                     merge_type: StoredMergeType::Squash,
                     sort_by: StoredSortBy::Created,
                     sort_direction: StoredSortDirection::Asc,
@@ -125,19 +130,25 @@ mod tests {
                         show_prs_from_untrusted_authors: false,
                         show_prs_with_unmatched_head: false,
                         skip_disqualifications_in_summary: false,
+                        show_draft_prs: false,
+                        show_stale_prs: false,
                     },
                 },
                 summary: StoredRunSummary {
-                    num_disqualifications: 1,
+                    num_disqualifications: 2,
                     num_errors: 2,
                     num_merged: 0,
+                    num_branches_updated: 0,
+                    num_enqueued: 0,
                 },
+                selected_repos: vec!["dhth/mrj".into(), "dhth/bmm".into()],
                 repos: vec![
                     StoredRepoRecord {
                         repo: "dhth/mrj".into(),
                         owner: "dhth".into(),
                         name: "mrj".into(),
                         status: StoredRepoStatus::Finished,
+                        settings: None,
                         error: None,
                         prs: vec![
                             StoredPrRecord {
@@ -154,6 +165,14 @@ mod tests {
                                         .single()
                                         .unwrap(),
                                 ),
+                                upgrade: Some(StoredUpgrade {
+                                    package: "clap".into(),
+                                    from: Some("4.5.39".into()),
+                                    to: "4.5.40".into(),
+                                    ecosystem: Some("cargo".into()),
+                                    update_type: Some(StoredUpdateType::Patch),
+                                }),
+                                ecosystem: Some("cargo".into()),
                                 status: StoredPrStatus::Disqualified,
                                 qualifications: vec![StoredQualification::Head {
                                     value: "dependabot/cargo/clap-4.5.40".into(),
@@ -163,6 +182,9 @@ mod tests {
                                 }),
                                 error: None,
                                 merged: false,
+                                head_sha: None,
+                                merge_commit_sha: None,
+                                post_merge_actions: vec![],
                             },
                             StoredPrRecord {
                                 number: 13,
@@ -178,6 +200,14 @@ mod tests {
                                         .single()
                                         .unwrap(),
                                 ),
+                                upgrade: Some(StoredUpgrade {
+                                    package: "tera".into(),
+                                    from: Some("1.19.0".into()),
+                                    to: "1.20.1".into(),
+                                    ecosystem: Some("cargo".into()),
+                                    update_type: Some(StoredUpdateType::Minor),
+                                }),
+                                ecosystem: Some("cargo".into()),
                                 status: StoredPrStatus::Qualified,
                                 qualifications: vec![
                                     StoredQualification::Head {
@@ -186,14 +216,25 @@ mod tests {
                                     StoredQualification::Author {
                                         value: "dependabot[bot]".into(),
                                     },
+                                    StoredQualification::UpdateType {
+                                        package: "tera".into(),
+                                        update_type: StoredUpdateType::Minor,
+                                    },
                                     StoredQualification::Check {
                                         name: "test".into(),
                                         conclusion: "success".into(),
                                     },
+                                    StoredQualification::IgnoredCheck {
+                                        name: "codecov/patch".into(),
+                                        conclusion: Some("failure".into()),
+                                    },
                                 ],
                                 disqualification: None,
                                 error: None,
                                 merged: false,
+                                head_sha: Some("7c4e9a1b2d3f5e6a8b9c0d1e2f3a4b5c6d7e8f90".into()),
+                                merge_commit_sha: None,
+                                post_merge_actions: vec![],
                             },
                             StoredPrRecord {
                                 number: 14,
@@ -209,6 +250,14 @@ mod tests {
                                         .single()
                                         .unwrap(),
                                 ),
+                                upgrade: Some(StoredUpgrade {
+                                    package: "regex".into(),
+                                    from: Some("1.12.2".into()),
+                                    to: "1.12.3".into(),
+                                    ecosystem: Some("cargo".into()),
+                                    update_type: Some(StoredUpdateType::Patch),
+                                }),
+                                ecosystem: Some("cargo".into()),
                                 status: StoredPrStatus::Errored,
                                 qualifications: vec![StoredQualification::Head {
                                     value: "dependabot/cargo/regex-1.12.3".into(),
@@ -216,6 +265,44 @@ mod tests {
                                 disqualification: None,
                                 error: Some("GitHub API returned a transient error".into()),
                                 merged: false,
+                                head_sha: None,
+                                merge_commit_sha: None,
+                                post_merge_actions: vec![],
+                            },
+                            StoredPrRecord {
+                                number: 15,
+                                title: "build: bump openssl from 0.10.72 to 0.10.73".into(),
+                                url: "https://github.com/dhth/mrj/pull/15".into(),
+                                created_at: Some(
+                                    Utc.with_ymd_and_hms(2025, 11, 2, 23, 9, 0)
+                                        .single()
+                                        .unwrap(),
+                                ),
+                                updated_at: Some(
+                                    Utc.with_ymd_and_hms(2025, 11, 2, 23, 16, 0)
+                                        .single()
+                                        .unwrap(),
+                                ),
+                                upgrade: Some(StoredUpgrade {
+                                    package: "openssl".into(),
+                                    from: Some("0.10.72".into()),
+                                    to: "0.10.73".into(),
+                                    ecosystem: Some("cargo".into()),
+                                    update_type: Some(StoredUpdateType::Patch),
+                                }),
+                                ecosystem: Some("cargo".into()),
+                                status: StoredPrStatus::Disqualified,
+                                qualifications: vec![StoredQualification::Head {
+                                    value: "dependabot/cargo/openssl-0.10.73".into(),
+                                }],
+                                disqualification: Some(StoredDisqualification::PackageDenied {
+                                    value: "openssl".into(),
+                                }),
+                                error: None,
+                                merged: false,
+                                head_sha: None,
+                                merge_commit_sha: None,
+                                post_merge_actions: vec![],
                             },
                         ],
                     },
@@ -224,6 +311,7 @@ mod tests {
                         owner: "dhth".into(),
                         name: "bmm".into(),
                         status: StoredRepoStatus::Errored,
+                        settings: None,
                         error: Some("couldn't fetch open PRs for repo".into()),
                         prs: vec![],
                     },
@@ -246,6 +334,7 @@ mod tests {
                     merge_if_blocked: false,
                     merge_if_checks_skipped: false,
                     merge_if_checks_neutral: true,
+                    allowed_update_types: None,
                     merge_type: StoredMergeType::Squash,
                     sort_by: StoredSortBy::Updated,
                     sort_direction: StoredSortDirection::Desc,
@@ -254,50 +343,114 @@ mod tests {
                         show_prs_from_untrusted_authors: false,
                         show_prs_with_unmatched_head: false,
                         skip_disqualifications_in_summary: false,
+                        show_draft_prs: false,
+                        show_stale_prs: false,
                     },
                 },
                 summary: StoredRunSummary {
                     num_disqualifications: 0,
                     num_errors: 0,
                     num_merged: 1,
+                    num_branches_updated: 0,
+                    num_enqueued: 1,
                 },
+                selected_repos: vec![],
                 repos: vec![StoredRepoRecord {
                     repo: "dhth/mrj".into(),
                     owner: "dhth".into(),
                     name: "mrj".into(),
                     status: StoredRepoStatus::Finished,
+                    settings: None,
                     error: None,
-                    prs: vec![StoredPrRecord {
-                        number: 11,
-                        title: "build: bump octocrab from 0.49.6 to 0.49.7".into(),
-                        url: "https://github.com/dhth/mrj/pull/11".into(),
-                        created_at: Some(
-                            Utc.with_ymd_and_hms(2025, 11, 1, 22, 20, 0)
-                                .single()
-                                .unwrap(),
-                        ),
-                        updated_at: Some(
-                            Utc.with_ymd_and_hms(2025, 11, 1, 22, 28, 0)
-                                .single()
-                                .unwrap(),
-                        ),
-                        status: StoredPrStatus::Qualified,
-                        qualifications: vec![
-                            StoredQualification::Head {
-                                value: "dependabot/cargo/octocrab-0.49.7".into(),
-                            },
-                            StoredQualification::Author {
-                                value: "dependabot[bot]".into(),
-                            },
-                            StoredQualification::Check {
-                                name: "advisory".into(),
-                                conclusion: "neutral".into(),
-                            },
-                        ],
-                        disqualification: None,
-                        error: None,
-                        merged: true,
-                    }],
+                    prs: vec![
+                        StoredPrRecord {
+                            number: 11,
+                            title: "build: bump octocrab from 0.49.6 to 0.49.7".into(),
+                            url: "https://github.com/dhth/mrj/pull/11".into(),
+                            created_at: Some(
+                                Utc.with_ymd_and_hms(2025, 11, 1, 22, 20, 0)
+                                    .single()
+                                    .unwrap(),
+                            ),
+                            updated_at: Some(
+                                Utc.with_ymd_and_hms(2025, 11, 1, 22, 28, 0)
+                                    .single()
+                                    .unwrap(),
+                            ),
+                            upgrade: Some(StoredUpgrade {
+                                package: "octocrab".into(),
+                                from: Some("0.49.6".into()),
+                                to: "0.49.7".into(),
+                                ecosystem: Some("cargo".into()),
+                                update_type: Some(StoredUpdateType::Patch),
+                            }),
+                            ecosystem: Some("cargo".into()),
+                            status: StoredPrStatus::Qualified,
+                            qualifications: vec![
+                                StoredQualification::Head {
+                                    value: "dependabot/cargo/octocrab-0.49.7".into(),
+                                },
+                                StoredQualification::Author {
+                                    value: "dependabot[bot]".into(),
+                                },
+                                StoredQualification::Check {
+                                    name: "advisory".into(),
+                                    conclusion: "neutral".into(),
+                                },
+                            ],
+                            disqualification: None,
+                            error: None,
+                            merged: true,
+                            head_sha: Some("3f1d2c9e8b7a6f5e4d3c2b1a0f9e8d7c6b5a4f3e".into()),
+                            merge_commit_sha: Some(
+                                "a1b2c3d4e5f60718293a4b5c6d7e8f9012345678".into(),
+                            ),
+                            post_merge_actions: vec![
+                                StoredPostMergeAction::DeleteBranch {
+                                    branch: "dependabot/cargo/octocrab-0.49.7".into(),
+                                    error: None,
+                                },
+                                StoredPostMergeAction::CloseSuperseded {
+                                    pr: 9,
+                                    error: Some("couldn't close superseded PR".into()),
+                                },
+                            ],
+                        },
+                        StoredPrRecord {
+                            number: 14,
+                            title: "build: bump tokio from 1.45.0 to 1.45.1".into(),
+                            url: "https://github.com/dhth/mrj/pull/14".into(),
+                            created_at: Some(
+                                Utc.with_ymd_and_hms(2025, 11, 1, 22, 24, 0)
+                                    .single()
+                                    .unwrap(),
+                            ),
+                            updated_at: None,
+                            upgrade: Some(StoredUpgrade {
+                                package: "tokio".into(),
+                                from: Some("1.45.0".into()),
+                                to: "1.45.1".into(),
+                                ecosystem: Some("cargo".into()),
+                                update_type: Some(StoredUpdateType::Patch),
+                            }),
+                            ecosystem: Some("cargo".into()),
+                            status: StoredPrStatus::AutoMergeEnabled,
+                            qualifications: vec![
+                                StoredQualification::Author {
+                                    value: "dependabot[bot]".into(),
+                                },
+                                StoredQualification::PendingCheck {
+                                    name: "test".into(),
+                                },
+                            ],
+                            disqualification: None,
+                            error: None,
+                            merged: false,
+                            head_sha: Some("5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f".into()),
+                            merge_commit_sha: None,
+                            post_merge_actions: vec![],
+                        },
+                    ],
                 }],
             },
         ]
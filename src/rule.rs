@@ -0,0 +1,807 @@
+use crate::domain::HumanDuration;
+use crate::upgrade::UpdateType;
+use regex::Regex;
+use serde::Deserialize;
+use serde::de::{self, Deserializer, Visitor};
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+/// A named rule, as configured via `[[rules]]`.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    pub name: String,
+    pub rule: RuleStatement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RuleAction {
+    Allow,
+    Deny,
+}
+
+impl Display for RuleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleAction::Allow => f.write_str("allow"),
+            RuleAction::Deny => f.write_str("deny"),
+        }
+    }
+}
+
+/// A statement in the form `<allow|deny> if <condition>`, where the condition is a boolean
+/// expression over a PR's attributes, eg. `allow if update_type == "patch" && ecosystem == "cargo"`.
+#[derive(Debug, Clone)]
+pub struct RuleStatement {
+    raw: String,
+    pub action: RuleAction,
+    condition: Expr,
+}
+
+/// What's known about a PR when rules are evaluated; an unknown value only satisfies "!="
+/// comparisons.
+#[derive(Debug, Default)]
+pub struct PrAttributes {
+    pub author: Option<String>,
+    pub title: String,
+    pub head: String,
+    pub base: String,
+    pub labels: Vec<String>,
+    pub ecosystem: Option<String>,
+    pub package: Option<String>,
+    pub update_type: Option<UpdateType>,
+    pub age: Option<Duration>,
+    pub changed_files: Option<u64>,
+    pub additions: Option<u64>,
+    pub files: Vec<String>,
+    pub checks: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(bool),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Comparison),
+}
+
+#[derive(Debug, Clone)]
+struct Comparison {
+    attribute: Attribute,
+    op: Op,
+    operand: Operand,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Attribute {
+    Author,
+    Title,
+    Head,
+    Base,
+    Labels,
+    Ecosystem,
+    Package,
+    UpdateType,
+    Age,
+    ChangedFiles,
+    Additions,
+    Files,
+    Checks,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    Matches,
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    Text(String),
+    Pattern(Regex),
+    UpdateType(UpdateType),
+    Number(u64),
+    Duration(Duration),
+}
+
+enum Value<'a> {
+    Text(Option<&'a str>),
+    List(&'a [String]),
+    UpdateType(Option<UpdateType>),
+    Number(Option<u64>),
+    Duration(Option<Duration>),
+}
+
+enum Literal {
+    Str(String),
+    Num(u64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(u64),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+impl RuleStatement {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(value)?,
+            pos: 0,
+        };
+
+        let action = match parser.next() {
+            Some(Token::Ident(w)) if w == "allow" => RuleAction::Allow,
+            Some(Token::Ident(w)) if w == "deny" => RuleAction::Deny,
+            _ => return Err(r#"a rule needs to start with "allow" or "deny""#.to_string()),
+        };
+
+        match parser.next() {
+            Some(Token::Ident(w)) if w == "if" => {}
+            _ => return Err(format!(r#"expected "if" after "{action}""#)),
+        }
+
+        let condition = parser.expr()?;
+        if let Some(token) = parser.next() {
+            return Err(format!("unexpected {token}"));
+        }
+
+        Ok(Self {
+            raw: value.trim().to_string(),
+            action,
+            condition,
+        })
+    }
+
+    pub fn is_match(&self, attributes: &PrAttributes) -> bool {
+        self.condition.evaluate(attributes)
+    }
+
+    /// Whether the rule looks at the paths a PR changes, which need to be fetched separately.
+    pub fn uses_files(&self) -> bool {
+        self.condition.uses(Attribute::Files)
+    }
+}
+
+impl Display for RuleStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for RuleStatement {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct RuleStatementVisitor;
+
+        impl Visitor<'_> for RuleStatementVisitor {
+            type Value = RuleStatement;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str(r#"a rule, eg. "allow if update_type == \"patch\"""#)
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                RuleStatement::parse(value)
+                    .map_err(|err| de::Error::custom(format!("invalid rule \"{value}\": {err}")))
+            }
+        }
+
+        deserializer.deserialize_str(RuleStatementVisitor)
+    }
+}
+
+#[cfg(test)]
+impl serde::Serialize for RuleStatement {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl Expr {
+    fn evaluate(&self, attributes: &PrAttributes) -> bool {
+        match self {
+            Expr::Literal(value) => *value,
+            Expr::Not(expr) => !expr.evaluate(attributes),
+            Expr::And(lhs, rhs) => lhs.evaluate(attributes) && rhs.evaluate(attributes),
+            Expr::Or(lhs, rhs) => lhs.evaluate(attributes) || rhs.evaluate(attributes),
+            Expr::Compare(comparison) => comparison.evaluate(attributes),
+        }
+    }
+
+    fn uses(&self, attribute: Attribute) -> bool {
+        match self {
+            Expr::Literal(_) => false,
+            Expr::Not(expr) => expr.uses(attribute),
+            Expr::And(lhs, rhs) | Expr::Or(lhs, rhs) => lhs.uses(attribute) || rhs.uses(attribute),
+            Expr::Compare(comparison) => comparison.attribute == attribute,
+        }
+    }
+}
+
+impl Comparison {
+    /// Only builds comparisons that make sense for the attribute, so that mistakes surface when
+    /// the config is parsed, rather than as rules that silently never match.
+    fn new(attribute: Attribute, op: Op, literal: Literal) -> Result<Self, String> {
+        let string = |literal| match literal {
+            Literal::Str(s) => Ok(s),
+            Literal::Num(_) => Err(format!(
+                r#""{attribute}" needs to be compared with a string"#
+            )),
+        };
+
+        let operand = match (attribute.value_kind(), op) {
+            (ValueKind::Text, Op::Eq | Op::Ne | Op::Contains) | (ValueKind::List, Op::Contains) => {
+                Operand::Text(string(literal)?)
+            }
+            (ValueKind::Text | ValueKind::List, Op::Matches) => {
+                let s = string(literal)?;
+                Operand::Pattern(
+                    Regex::new(&s).map_err(|err| format!("invalid regex \"{s}\": {err}"))?,
+                )
+            }
+            (ValueKind::UpdateType, Op::Eq | Op::Ne) => {
+                let s = string(literal)?;
+                Operand::UpdateType(parse_update_type(&s).ok_or_else(|| {
+                    format!(
+                        r#"unknown update type "{s}" (expected "major", "minor", "patch", or "prerelease")"#
+                    )
+                })?)
+            }
+            (ValueKind::Number, Op::Eq | Op::Ne | Op::Lt | Op::Le | Op::Gt | Op::Ge) => {
+                match literal {
+                    Literal::Num(n) => Operand::Number(n),
+                    Literal::Str(_) => {
+                        return Err(format!(
+                            r#""{attribute}" needs to be compared with a number"#
+                        ));
+                    }
+                }
+            }
+            (ValueKind::Duration, Op::Lt | Op::Le | Op::Gt | Op::Ge) => {
+                let s = string(literal).map_err(|_| {
+                    format!(r#""{attribute}" needs to be compared with a duration, eg. "3d""#)
+                })?;
+                Operand::Duration(
+                    HumanDuration::parse(&s)
+                        .ok_or_else(|| format!("invalid duration \"{s}\""))?
+                        .duration,
+                )
+            }
+            _ => return Err(format!(r#""{op}" can't be used with "{attribute}""#)),
+        };
+
+        Ok(Self {
+            attribute,
+            op,
+            operand,
+        })
+    }
+
+    fn evaluate(&self, attributes: &PrAttributes) -> bool {
+        // "!=" holds whenever "==" doesn't, including for values that aren't known
+        match self.op {
+            Op::Ne => !self.holds(Op::Eq, attributes),
+            op => self.holds(op, attributes),
+        }
+    }
+
+    fn holds(&self, op: Op, attributes: &PrAttributes) -> bool {
+        match (self.attribute.value(attributes), &self.operand) {
+            (Value::Text(Some(value)), Operand::Text(text)) => match op {
+                Op::Eq => value == text,
+                Op::Contains => value.contains(text.as_str()),
+                _ => false,
+            },
+            (Value::Text(Some(value)), Operand::Pattern(re)) => re.is_match(value),
+            (Value::List(values), Operand::Text(text)) => values.iter().any(|v| v == text),
+            (Value::List(values), Operand::Pattern(re)) => values.iter().any(|v| re.is_match(v)),
+            (Value::UpdateType(Some(value)), Operand::UpdateType(update_type)) => match op {
+                Op::Eq => value == *update_type,
+                _ => false,
+            },
+            (Value::Number(Some(value)), Operand::Number(number)) => op.compare(value, *number),
+            (Value::Duration(Some(value)), Operand::Duration(duration)) => {
+                op.compare(value, *duration)
+            }
+            _ => false,
+        }
+    }
+}
+
+enum ValueKind {
+    Text,
+    List,
+    UpdateType,
+    Number,
+    Duration,
+}
+
+impl Attribute {
+    fn parse(name: &str) -> Option<Self> {
+        let attribute = match name {
+            "author" => Attribute::Author,
+            "title" => Attribute::Title,
+            "head" => Attribute::Head,
+            "base" => Attribute::Base,
+            "labels" => Attribute::Labels,
+            "ecosystem" => Attribute::Ecosystem,
+            "package" => Attribute::Package,
+            "update_type" => Attribute::UpdateType,
+            "age" => Attribute::Age,
+            "changed_files" => Attribute::ChangedFiles,
+            "additions" => Attribute::Additions,
+            "files" => Attribute::Files,
+            "checks" => Attribute::Checks,
+            _ => return None,
+        };
+
+        Some(attribute)
+    }
+
+    fn value_kind(self) -> ValueKind {
+        match self {
+            Attribute::Author
+            | Attribute::Title
+            | Attribute::Head
+            | Attribute::Base
+            | Attribute::Ecosystem
+            | Attribute::Package => ValueKind::Text,
+            Attribute::Labels | Attribute::Files | Attribute::Checks => ValueKind::List,
+            Attribute::UpdateType => ValueKind::UpdateType,
+            Attribute::ChangedFiles | Attribute::Additions => ValueKind::Number,
+            Attribute::Age => ValueKind::Duration,
+        }
+    }
+
+    fn value(self, attributes: &PrAttributes) -> Value<'_> {
+        match self {
+            Attribute::Author => Value::Text(attributes.author.as_deref()),
+            Attribute::Title => Value::Text(Some(&attributes.title)),
+            Attribute::Head => Value::Text(Some(&attributes.head)),
+            Attribute::Base => Value::Text(Some(&attributes.base)),
+            Attribute::Labels => Value::List(&attributes.labels),
+            Attribute::Ecosystem => Value::Text(attributes.ecosystem.as_deref()),
+            Attribute::Package => Value::Text(attributes.package.as_deref()),
+            Attribute::UpdateType => Value::UpdateType(attributes.update_type),
+            Attribute::Age => Value::Duration(attributes.age),
+            Attribute::ChangedFiles => Value::Number(attributes.changed_files),
+            Attribute::Additions => Value::Number(attributes.additions),
+            Attribute::Files => Value::List(&attributes.files),
+            Attribute::Checks => Value::List(&attributes.checks),
+        }
+    }
+}
+
+impl Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Attribute::Author => "author",
+            Attribute::Title => "title",
+            Attribute::Head => "head",
+            Attribute::Base => "base",
+            Attribute::Labels => "labels",
+            Attribute::Ecosystem => "ecosystem",
+            Attribute::Package => "package",
+            Attribute::UpdateType => "update_type",
+            Attribute::Age => "age",
+            Attribute::ChangedFiles => "changed_files",
+            Attribute::Additions => "additions",
+            Attribute::Files => "files",
+            Attribute::Checks => "checks",
+        };
+
+        f.write_str(value)
+    }
+}
+
+impl Op {
+    fn compare<T: PartialOrd>(self, lhs: T, rhs: T) -> bool {
+        match self {
+            Op::Eq => lhs == rhs,
+            Op::Ne => lhs != rhs,
+            Op::Lt => lhs < rhs,
+            Op::Le => lhs <= rhs,
+            Op::Gt => lhs > rhs,
+            Op::Ge => lhs >= rhs,
+            Op::Contains | Op::Matches => false,
+        }
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Op::Eq => "==",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Contains => "contains",
+            Op::Matches => "matches",
+        };
+
+        f.write_str(value)
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(w) => write!(f, "\"{w}\""),
+            Token::Str(s) => write!(f, "string \"{s}\""),
+            Token::Num(n) => write!(f, "number {n}"),
+            Token::Op(op) => write!(f, "\"{op}\""),
+            Token::And => f.write_str("\"&&\""),
+            Token::Or => f.write_str("\"||\""),
+            Token::Not => f.write_str("\"!\""),
+            Token::LParen => f.write_str("\"(\""),
+            Token::RParen => f.write_str("\")\""),
+        }
+    }
+}
+
+fn parse_update_type(value: &str) -> Option<UpdateType> {
+    match value {
+        "major" => Some(UpdateType::Major),
+        "minor" => Some(UpdateType::Minor),
+        "patch" => Some(UpdateType::Patch),
+        "prerelease" => Some(UpdateType::Prerelease),
+        _ => None,
+    }
+}
+
+fn tokenize(value: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped @ ('"' | '\\')) => s.push(escaped),
+                            _ => return Err(r#"only \" and \\ can be escaped in strings"#.into()),
+                        },
+                        Some(c) => s.push(c),
+                        None => return Err("unterminated string".into()),
+                    }
+                }
+                Token::Str(s)
+            }
+            c if c.is_ascii_digit() => {
+                let mut n = u64::from(c.to_digit(10).unwrap_or_default());
+                while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                    n = n
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(u64::from(digit)))
+                        .ok_or("number is too large")?;
+                    chars.next();
+                }
+                Token::Num(n)
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut word = c.to_string();
+                while let Some(&c) = chars.peek()
+                    && (c.is_ascii_alphanumeric() || c == '_')
+                {
+                    word.push(c);
+                    chars.next();
+                }
+                match word.as_str() {
+                    "contains" => Token::Op(Op::Contains),
+                    "matches" => Token::Op(Op::Matches),
+                    _ => Token::Ident(word),
+                }
+            }
+            _ => {
+                let (token, width) = match (c, chars.peek()) {
+                    ('=', Some('=')) => (Token::Op(Op::Eq), 2),
+                    ('!', Some('=')) => (Token::Op(Op::Ne), 2),
+                    ('<', Some('=')) => (Token::Op(Op::Le), 2),
+                    ('>', Some('=')) => (Token::Op(Op::Ge), 2),
+                    ('&', Some('&')) => (Token::And, 2),
+                    ('|', Some('|')) => (Token::Or, 2),
+                    ('<', _) => (Token::Op(Op::Lt), 1),
+                    ('>', _) => (Token::Op(Op::Gt), 1),
+                    ('!', _) => (Token::Not, 1),
+                    _ => return Err(format!("unexpected character '{c}'")),
+                };
+                if width == 2 {
+                    chars.next();
+                }
+                token
+            }
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser for conditions, where `!` binds tighter than `&&`, which binds
+/// tighter than `||`.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            lhs = Expr::And(Box::new(lhs), Box::new(self.unary()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.expr()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err(r#"expected ")""#.to_string()),
+                }
+            }
+            Some(Token::Ident(w)) if w == "true" => Ok(Expr::Literal(true)),
+            Some(Token::Ident(w)) if w == "false" => Ok(Expr::Literal(false)),
+            Some(Token::Ident(name)) => {
+                let attribute = Attribute::parse(&name)
+                    .ok_or_else(|| format!("unknown attribute \"{name}\""))?;
+                let op = match self.next() {
+                    Some(Token::Op(op)) => op,
+                    _ => return Err(format!("expected an operator after \"{name}\"")),
+                };
+                let literal = match self.next() {
+                    Some(Token::Str(s)) => Literal::Str(s),
+                    Some(Token::Num(n)) => Literal::Num(n),
+                    _ => return Err(format!("expected a string or a number after \"{op}\"")),
+                };
+
+                Comparison::new(attribute, op, literal).map(Expr::Compare)
+            }
+            Some(token) => Err(format!("unexpected {token}")),
+            None => Err("unexpected end of rule".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_yaml_snapshot;
+
+    #[test]
+    fn evaluating_rules_works() {
+        // GIVEN
+        let attributes = attributes();
+        let rules = [
+            r#"allow if update_type == "patch" && ecosystem == "cargo""#,
+            r#"allow if update_type != "major""#,
+            r#"deny if title contains "[security]""#,
+            r#"allow if author == "dependabot[bot]" && labels contains "dependencies""#,
+            r#"allow if head matches "^dependabot/cargo/" && base == "main""#,
+            r#"allow if package matches "^serde""#,
+            r#"allow if age >= "3d" && age < "1w""#,
+            r#"allow if changed_files <= 2 && additions < 10"#,
+            r#"allow if files contains "Cargo.lock" && !(files matches "^src/")"#,
+            r#"allow if checks contains "test" || checks contains "lint""#,
+        ];
+
+        // WHEN
+        let results = rules.map(|rule| {
+            RuleStatement::parse(rule)
+                .expect("rule should've been parsed")
+                .is_match(&attributes)
+        });
+
+        // THEN
+        assert_eq!(
+            results,
+            [true, true, false, true, true, false, true, true, true, true]
+        );
+    }
+
+    #[test]
+    fn unknown_values_only_satisfy_inequalities() {
+        // GIVEN
+        let attributes = PrAttributes::default();
+        let rules = [
+            r#"allow if ecosystem == "cargo""#,
+            r#"allow if ecosystem != "cargo""#,
+            r#"allow if update_type != "major""#,
+            r#"allow if age > "1d""#,
+            r#"allow if changed_files < 100"#,
+            r#"allow if !(ecosystem == "cargo")"#,
+            r#"allow if changed_files != 100"#,
+        ];
+
+        // WHEN
+        let results = rules.map(|rule| {
+            RuleStatement::parse(rule)
+                .expect("rule should've been parsed")
+                .is_match(&attributes)
+        });
+
+        // THEN
+        assert_eq!(results, [false, true, true, false, false, true, true]);
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        // GIVEN
+        let attributes = PrAttributes::default();
+        let rules = [
+            "allow if true || false && false",
+            "allow if (true || false) && false",
+            "allow if !false && !true || true",
+            "deny if !!true",
+        ];
+
+        // WHEN
+        let results = rules.map(|rule| {
+            RuleStatement::parse(rule)
+                .expect("rule should've been parsed")
+                .is_match(&attributes)
+        });
+
+        // THEN
+        assert_eq!(results, [true, false, true, true]);
+    }
+
+    #[test]
+    fn rules_know_whether_they_use_files() {
+        // GIVEN
+        let with_files =
+            RuleStatement::parse(r#"deny if title contains "x" || files contains "x""#)
+                .expect("rule should've been parsed");
+        let without_files = RuleStatement::parse(r#"deny if changed_files > 10"#)
+            .expect("rule should've been parsed");
+
+        // WHEN
+        // THEN
+        assert!(with_files.uses_files());
+        assert!(!without_files.uses_files());
+    }
+
+    #[test]
+    fn parsing_invalid_rules_fails() {
+        // GIVEN
+        let rules = [
+            r#"merge if title contains "x""#,
+            r#"allow title contains "x""#,
+            r#"allow if"#,
+            r#"allow if reviewer == "x""#,
+            r#"allow if title"#,
+            r#"allow if title contains"#,
+            r#"allow if title < "x""#,
+            r#"allow if title == 1"#,
+            r#"allow if update_type == "huge""#,
+            r#"allow if changed_files == "3""#,
+            r#"allow if age > 3"#,
+            r#"allow if age > "3 days""#,
+            r#"allow if age == "3d""#,
+            r#"allow if labels == "x""#,
+            r#"allow if head matches "(""#,
+            r#"allow if (title contains "x""#,
+            r#"allow if title contains "x")"#,
+            r#"allow if title contains "x"#,
+            r#"allow if title contains "x" & true"#,
+        ];
+
+        // WHEN
+        let errors = rules.map(|rule| {
+            RuleStatement::parse(rule)
+                .map(|_| ())
+                .expect_err("rule shouldn't have been parsed")
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        });
+
+        // THEN
+        assert_yaml_snapshot!(errors, @r#"
+        - "a rule needs to start with \"allow\" or \"deny\""
+        - "expected \"if\" after \"allow\""
+        - unexpected end of rule
+        - "unknown attribute \"reviewer\""
+        - "expected an operator after \"title\""
+        - "expected a string or a number after \"contains\""
+        - "\"<\" can't be used with \"title\""
+        - "\"title\" needs to be compared with a string"
+        - "unknown update type \"huge\" (expected \"major\", \"minor\", \"patch\", or \"prerelease\")"
+        - "\"changed_files\" needs to be compared with a number"
+        - "\"age\" needs to be compared with a duration, eg. \"3d\""
+        - "invalid duration \"3 days\""
+        - "\"==\" can't be used with \"age\""
+        - "\"==\" can't be used with \"labels\""
+        - "invalid regex \"(\": regex parse error:"
+        - "expected \")\""
+        - "unexpected \")\""
+        - unterminated string
+        - "unexpected character '&'"
+        "#);
+    }
+
+    fn attributes() -> PrAttributes {
+        PrAttributes {
+            author: Some("dependabot[bot]".to_string()),
+            title: "build(deps): bump clap from 4.5.39 to 4.5.40".to_string(),
+            head: "dependabot/cargo/clap-4.5.40".to_string(),
+            base: "main".to_string(),
+            labels: vec!["dependencies".to_string(), "rust".to_string()],
+            ecosystem: Some("cargo".to_string()),
+            package: Some("clap".to_string()),
+            update_type: Some(UpdateType::Patch),
+            age: Some(Duration::from_secs(4 * 24 * 60 * 60)),
+            changed_files: Some(2),
+            additions: Some(6),
+            files: vec!["Cargo.toml".to_string(), "Cargo.lock".to_string()],
+            checks: vec!["test".to_string(), "build (ubuntu-latest)".to_string()],
+        }
+    }
+}
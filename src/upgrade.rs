@@ -0,0 +1,582 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::Regex;
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::sync::LazyLock;
+
+#[allow(clippy::expect_used)]
+static DEPENDABOT_TITLE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bbump (?P<package>\S+) from (?P<from>\S+) to (?P<to>\S+)")
+        .expect("regex for dependabot titles should've been built")
+});
+
+#[allow(clippy::expect_used)]
+static RENOVATE_TITLE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\bupdate (?:(?P<kind>rust crate|dependency|module|docker image) )?(?P<package>\S+?)(?: (?P<suffix>action|docker tag))? to (?P<to>\S+)",
+    )
+    .expect("regex for renovate titles should've been built")
+});
+
+#[allow(clippy::expect_used)]
+static BODY_VERSIONS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(?:\bfrom `?(?P<from>v?\d[^\s`]*)`? to `?(?P<to>v?\d[^\s`]*)`?)|(?:`(?P<from_arrow>v?\d[^\s`]*)` -> `(?P<to_arrow>v?\d[^\s`]*)`)",
+    )
+    .expect("regex for versions in PR bodies should've been built")
+});
+
+#[allow(clippy::expect_used)]
+static BODY_VERSION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bv?\d+\.\d+(?:\.\d+)?(?:-[0-9A-Za-z.]+)?")
+        .expect("regex for versions in PR bodies should've been built")
+});
+
+#[allow(clippy::expect_used)]
+static BODY_DATE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b\d{4}-\d{2}-\d{2}\b").expect("regex for dates in PR bodies should've been built")
+});
+
+/// A dependency upgrade, as described by the title (and body) of a PR created by Dependabot or
+/// Renovate.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct Upgrade {
+    pub package: String,
+    pub from: Option<Version>,
+    pub to: Version,
+    pub ecosystem: Option<String>,
+}
+
+impl Upgrade {
+    /// Parses the upgrade from the PR's title (and body), falling back to Dependabot's head ref
+    /// when the title isn't recognised.
+    pub fn parse(title: &str, body: Option<&str>, head_ref: &str) -> Option<Self> {
+        Self::parse_title(title, body, head_ref).or_else(|| Self::parse_head_ref(head_ref))
+    }
+
+    fn parse_title(title: &str, body: Option<&str>, head_ref: &str) -> Option<Self> {
+        if let Some(caps) = DEPENDABOT_TITLE_RE.captures(title) {
+            return Some(Self {
+                package: caps["package"].to_string(),
+                from: Version::parse(&caps["from"]),
+                to: Version::parse(&caps["to"])?,
+                ecosystem: ecosystem_from_head_ref(head_ref),
+            });
+        }
+
+        let caps = RENOVATE_TITLE_RE.captures(title)?;
+        let to = Version::parse(&caps["to"])?;
+        let from = body.and_then(|b| from_version_in_body(b, &to));
+        let ecosystem = match (
+            caps.name("kind").map(|k| k.as_str().to_lowercase()),
+            caps.name("suffix").map(|s| s.as_str().to_lowercase()),
+        ) {
+            (_, Some(suffix)) if suffix == "action" => Some("github_actions".to_string()),
+            (_, Some(suffix)) if suffix == "docker tag" => Some("docker".to_string()),
+            (Some(kind), _) if kind == "rust crate" => Some("cargo".to_string()),
+            (Some(kind), _) if kind == "module" => Some("gomod".to_string()),
+            (Some(kind), _) if kind == "docker image" => Some("docker".to_string()),
+            _ => None,
+        };
+
+        Some(Self {
+            package: caps["package"].to_string(),
+            from,
+            to,
+            ecosystem,
+        })
+    }
+
+    /// Dependabot's head refs look like "dependabot/<ecosystem>/<package>-<version>"; packages
+    /// with a "/" in their name (eg. "actions/checkout") keep it.
+    fn parse_head_ref(head_ref: &str) -> Option<Self> {
+        let ecosystem = ecosystem_from_head_ref(head_ref)?;
+        let rest = head_ref.strip_prefix(&format!("dependabot/{ecosystem}/"))?;
+        let (package, version) = rest.rsplit_once('-')?;
+        if package.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            package: package.to_string(),
+            from: None,
+            to: Version::parse(version)?,
+            ecosystem: Some(ecosystem),
+        })
+    }
+
+    /// The kind of update, if it can be determined; this requires the version being upgraded from
+    /// to be known.
+    pub fn update_type(&self) -> Option<UpdateType> {
+        if self.to.pre.is_some() {
+            return Some(UpdateType::Prerelease);
+        }
+
+        let from = self.from.as_ref()?;
+
+        // as per semver, anything may change before 1.0.0; a minor bump there is a breaking one
+        if from.major != self.to.major || (from.major == 0 && from.minor != self.to.minor) {
+            Some(UpdateType::Major)
+        } else if from.minor != self.to.minor {
+            Some(UpdateType::Minor)
+        } else {
+            Some(UpdateType::Patch)
+        }
+    }
+}
+
+/// A leniently parsed version; "v2", "1.2" and "1.2.3-beta.1" are all valid.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct Version {
+    pub raw: String,
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Option<String>,
+}
+
+impl Version {
+    pub fn parse(value: &str) -> Option<Self> {
+        let raw = value.trim_end_matches(['.', ',', ')']);
+        let without_prefix = raw.strip_prefix(['v', 'V']).unwrap_or(raw);
+        let without_build = without_prefix
+            .split_once('+')
+            .map_or(without_prefix, |(v, _)| v);
+        let (core, pre) = match without_build.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (without_build, None),
+        };
+
+        let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+        let major = parts.next()??;
+        let minor = parts.next().unwrap_or(Some(0))?;
+        let patch = parts.next().unwrap_or(Some(0))?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            raw: raw.to_string(),
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    pub fn same_as(&self, other: &Version) -> bool {
+        self.major == other.major
+            && self.minor == other.minor
+            && self.patch == other.patch
+            && self.pre == other.pre
+    }
+
+    /// Whether this version isn't newer than `other`; a pre-release comes before the release it
+    /// leads up to, and pre-releases of the same version are compared by their labels.
+    pub fn at_most(&self, other: &Version) -> bool {
+        match (self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch)) {
+            Ordering::Less => true,
+            Ordering::Greater => false,
+            Ordering::Equal => match (&self.pre, &other.pre) {
+                (Some(pre), Some(other_pre)) => pre <= other_pre,
+                (Some(_), None) | (None, None) => true,
+                (None, Some(_)) => false,
+            },
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdateType {
+    Major,
+    Minor,
+    Patch,
+    Prerelease,
+}
+
+impl Display for UpdateType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            UpdateType::Major => "major",
+            UpdateType::Minor => "minor",
+            UpdateType::Patch => "patch",
+            UpdateType::Prerelease => "prerelease",
+        };
+
+        write!(f, "{value}")
+    }
+}
+
+/// The bot that created a PR.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub enum UpdateBot {
+    Dependabot,
+    Renovate,
+}
+
+impl Display for UpdateBot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            UpdateBot::Dependabot => "dependabot",
+            UpdateBot::Renovate => "renovate",
+        };
+
+        write!(f, "{value}")
+    }
+}
+
+/// The bot that created a PR, as per its author (eg. "dependabot[bot]"), or else as per its head
+/// ref (eg. "renovate/serde-monorepo"), which also covers self-hosted Renovate.
+pub fn detect_bot(author: Option<&str>, head_ref: &str) -> Option<UpdateBot> {
+    let from_author = author.and_then(|a| match a {
+        "dependabot[bot]" => Some(UpdateBot::Dependabot),
+        "renovate[bot]" => Some(UpdateBot::Renovate),
+        _ => None,
+    });
+
+    from_author.or_else(|| match head_ref.split_once('/') {
+        Some(("dependabot", _)) => Some(UpdateBot::Dependabot),
+        Some(("renovate", _)) => Some(UpdateBot::Renovate),
+        _ => None,
+    })
+}
+
+/// The ecosystem a PR's upgrade belongs to, as per Dependabot's head ref (eg.
+/// "dependabot/cargo/clap-4.5.40"), or else as per the kind of upgrade in Renovate's title (eg.
+/// "update rust crate serde to v1.0.200").
+pub fn detect_ecosystem(title: &str, body: Option<&str>, head_ref: &str) -> Option<String> {
+    ecosystem_from_head_ref(head_ref)
+        .or_else(|| Upgrade::parse_title(title, body, head_ref).and_then(|u| u.ecosystem))
+}
+
+fn ecosystem_from_head_ref(head_ref: &str) -> Option<String> {
+    let mut segments = head_ref.split('/');
+
+    match (segments.next(), segments.next(), segments.next()) {
+        (Some("dependabot"), Some(ecosystem), Some(_)) if !ecosystem.is_empty() => {
+            Some(ecosystem.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Finds when a version was released, as per the release notes/changelog in a PR's body; these
+/// usually have lines like "## [1.0.229] - 2025-03-01". As only the day is known, the end of it is
+/// returned, so as to never make a release seem older than it is.
+pub fn release_date(body: &str, version: &Version) -> Option<DateTime<Utc>> {
+    body.lines().find_map(|line| {
+        let date = BODY_DATE_RE.find(line)?;
+
+        let mentions_version = BODY_VERSION_RE
+            .find_iter(line)
+            .filter_map(|v| Version::parse(v.as_str()))
+            .any(|v| v.same_as(version));
+        if !mentions_version {
+            return None;
+        }
+
+        NaiveDate::parse_from_str(date.as_str(), "%Y-%m-%d")
+            .ok()?
+            .succ_opt()?
+            .and_hms_opt(0, 0, 0)
+            .map(|d| d.and_utc())
+    })
+}
+
+fn from_version_in_body(body: &str, to: &Version) -> Option<Version> {
+    BODY_VERSIONS_RE.captures_iter(body).find_map(|caps| {
+        let (from, body_to) = match (caps.name("from"), caps.name("to")) {
+            (Some(from), Some(to)) => (from, to),
+            _ => (caps.name("from_arrow")?, caps.name("to_arrow")?),
+        };
+
+        if !Version::parse(body_to.as_str())?.same_as(to) {
+            return None;
+        }
+
+        Version::parse(from.as_str())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_yaml_snapshot;
+
+    #[test]
+    fn finding_release_date_in_body_works() {
+        // GIVEN
+        let body = r#"Bumps [serde](https://github.com/serde-rs/serde) from 1.0.228 to 1.0.229.
+<details>
+<summary>Changelog</summary>
+<h2>[1.0.229] - 2025-03-01</h2>
+<h2>[1.0.228] - 2025-02-20</h2>
+</details>"#;
+        let version = Version::parse("1.0.229").expect("version should've been parsed");
+
+        // WHEN
+        let released_at = release_date(body, &version);
+
+        // THEN
+        assert_eq!(
+            released_at.map(|r| r.to_rfc3339()).as_deref(),
+            Some("2025-03-02T00:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn finding_release_date_ignores_other_versions() {
+        // GIVEN
+        let body = r#"## v1.2.30 (2025-03-01)
+## v1.2.2 (2025-02-01)
+Released 1.2.3 to crates.io"#;
+        let version = Version::parse("v1.2.3").expect("version should've been parsed");
+
+        // WHEN
+        let released_at = release_date(body, &version);
+
+        // THEN
+        assert!(released_at.is_none());
+    }
+
+    #[test]
+    fn parsing_dependabot_title_works() {
+        // GIVEN
+        let title = "build(deps): bump clap from 4.5.39 to 4.5.40";
+
+        // WHEN
+        let upgrade = Upgrade::parse(title, None, "dependabot/cargo/clap-4.5.40");
+
+        // THEN
+        assert_yaml_snapshot!(upgrade, @r#"
+        package: clap
+        from:
+          raw: 4.5.39
+          major: 4
+          minor: 5
+          patch: 39
+          pre: ~
+        to:
+          raw: 4.5.40
+          major: 4
+          minor: 5
+          patch: 40
+          pre: ~
+        ecosystem: cargo
+        "#);
+    }
+
+    #[test]
+    fn parsing_dependabot_title_with_directory_works() {
+        // GIVEN
+        let title = "Bump @types/node from 20.1.0 to 20.2.0 in /web";
+
+        // WHEN
+        let upgrade = Upgrade::parse(title, None, "dependabot/npm_and_yarn/web/types/node-20.2.0")
+            .expect("upgrade should've been parsed");
+
+        // THEN
+        assert_eq!(upgrade.package, "@types/node");
+        assert_eq!(upgrade.ecosystem.as_deref(), Some("npm_and_yarn"));
+        assert_eq!(upgrade.update_type(), Some(UpdateType::Minor));
+    }
+
+    #[test]
+    fn parsing_renovate_title_uses_body_for_from_version() {
+        // GIVEN
+        let title = "chore(deps): update rust crate serde to v1.0.200";
+        let body = r#"
+| Package | Type | Update | Change |
+|---|---|---|---|
+| [serde](https://serde.rs) | dependencies | patch | `1.0.199` -> `1.0.200` |
+"#;
+
+        // WHEN
+        let upgrade = Upgrade::parse(title, Some(body), "renovate/serde-monorepo");
+
+        // THEN
+        assert_yaml_snapshot!(upgrade, @r#"
+        package: serde
+        from:
+          raw: 1.0.199
+          major: 1
+          minor: 0
+          patch: 199
+          pre: ~
+        to:
+          raw: v1.0.200
+          major: 1
+          minor: 0
+          patch: 200
+          pre: ~
+        ecosystem: cargo
+        "#);
+    }
+
+    #[test]
+    fn parsing_renovate_action_title_works() {
+        // GIVEN
+        let title = "Update actions/checkout action to v4";
+
+        // WHEN
+        let upgrade = Upgrade::parse(title, None, "renovate/actions-checkout-4.x")
+            .expect("upgrade should've been parsed");
+
+        // THEN
+        assert_eq!(upgrade.package, "actions/checkout");
+        assert_eq!(upgrade.ecosystem.as_deref(), Some("github_actions"));
+        assert!(upgrade.from.is_none());
+        assert!(upgrade.update_type().is_none());
+    }
+
+    #[test]
+    fn parsing_falls_back_to_dependabot_head_ref() {
+        // GIVEN
+        let title = "Bump the github-actions group with 1 update";
+
+        // WHEN
+        let upgrade = Upgrade::parse(
+            title,
+            None,
+            "dependabot/github_actions/actions/checkout-4.2.2",
+        );
+
+        // THEN
+        assert_yaml_snapshot!(upgrade, @"
+        package: actions/checkout
+        from: ~
+        to:
+          raw: 4.2.2
+          major: 4
+          minor: 2
+          patch: 2
+          pre: ~
+        ecosystem: github_actions
+        ");
+    }
+
+    #[test]
+    fn parsing_unrelated_title_fails() {
+        // GIVEN
+        let titles = [
+            "feat: add support for topics",
+            "Bump the cargo group across 1 directory with 3 updates",
+            "bump clap from 4.5.39 to latest",
+        ];
+
+        // WHEN
+        // THEN
+        for title in titles {
+            assert!(
+                Upgrade::parse(title, None, "main").is_none(),
+                "title: {title}"
+            );
+        }
+    }
+
+    #[test]
+    fn detecting_ecosystem_works() {
+        // GIVEN
+        let cases = [
+            (
+                "Bump the cargo group with 2 updates",
+                "dependabot/cargo/cargo-group-a1b2c3",
+                Some("cargo"),
+            ),
+            (
+                "Update actions/checkout action to v4",
+                "renovate/actions-checkout-4.x",
+                Some("github_actions"),
+            ),
+            (
+                "Update dependency eslint to v9",
+                "renovate/eslint-9.x",
+                None,
+            ),
+            ("feat: add support for topics", "add-topics", None),
+        ];
+
+        // WHEN
+        // THEN
+        for (title, head_ref, expected) in cases {
+            assert_eq!(
+                detect_ecosystem(title, None, head_ref).as_deref(),
+                expected,
+                "title: {title}"
+            );
+        }
+    }
+
+    #[test]
+    fn detecting_bot_works() {
+        // GIVEN
+        let cases = [
+            (
+                Some("dependabot[bot]"),
+                "dependabot/cargo/clap-4.5.40",
+                Some(UpdateBot::Dependabot),
+            ),
+            (
+                Some("renovate[bot]"),
+                "renovate/serde-monorepo",
+                Some(UpdateBot::Renovate),
+            ),
+            (
+                Some("self-hosted-renovate"),
+                "renovate/actions-checkout-4.x",
+                Some(UpdateBot::Renovate),
+            ),
+            (
+                None,
+                "dependabot/npm_and_yarn/eslint-9.0.0",
+                Some(UpdateBot::Dependabot),
+            ),
+            (Some("octocat"), "add-topics", None),
+            (Some("octocat"), "renovate-config", None),
+        ];
+
+        // WHEN
+        // THEN
+        for (author, head_ref, expected) in cases {
+            assert_eq!(
+                detect_bot(author, head_ref),
+                expected,
+                "head_ref: {head_ref}"
+            );
+        }
+    }
+
+    #[test]
+    fn update_type_is_determined_correctly() {
+        // GIVEN
+        let cases = [
+            ("1.2.3", "1.2.4", UpdateType::Patch),
+            ("1.2.3", "1.3.0", UpdateType::Minor),
+            ("1.2.3", "2.0.0", UpdateType::Major),
+            ("v3", "v4", UpdateType::Major),
+            ("0.1.2", "0.1.3", UpdateType::Patch),
+            ("0.1.2", "0.2.0", UpdateType::Major),
+            ("1.2.3", "1.3.0-rc.1", UpdateType::Prerelease),
+        ];
+
+        // WHEN
+        // THEN
+        for (from, to, expected) in cases {
+            let title = format!("bump x from {from} to {to}");
+            let upgrade = Upgrade::parse(&title, None, "").expect("upgrade should've been parsed");
+            assert_eq!(upgrade.update_type(), Some(expected), "{from} -> {to}");
+        }
+    }
+}
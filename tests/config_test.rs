@@ -91,13 +91,42 @@ fn printing_sample_config_works() {
     # mrj.toml
 
     # repos to run for
-    # (required)
+    # each entry can be one of:
+    # - "owner/repo": a single repo
+    # - "owner/<glob>": all repos of the owner whose name matches the glob
+    # - { owner = "owner", topic = "topic" }: all repos of the owner with the topic
+    # (required, unless "search" is set)
     repos = [
         "owner/repo-1",
         "owner/repo-2",
-        "owner/repo-3",
+        "owner/service-*",
+        { owner = "owner", topic = "auto-merge" },
     ]
 
+    # instead of listing open PRs repo by repo, mrj can find candidate PRs via a
+    # GitHub search query; this is a lot cheaper for owners with many repos, and
+    # also covers repos not listed above
+    # when set, "repos" is not used to find PRs (but "--repos" still takes
+    # precedence); found PRs that are closed, or whose authors aren't trusted, are
+    # left out without being fetched
+    # read more on the syntax here
+    # https://docs.github.com/en/search-github/searching-on-github/searching-issues-and-pull-requests
+    # (optional, default: empty)
+    # search = "is:pr is:open author:app/dependabot org:owner"
+
+    # repos to leave out, matched against "owner/repo"
+    # applies to repos from all the entries above, as well as to search results
+    # (optional, default: empty)
+    exclude = ["owner/service-legacy"]
+
+    # whether to leave out archived repos when expanding globs and topics
+    # (optional, default: true)
+    skip_archived = true
+
+    # whether to leave out forks when expanding globs and topics
+    # (optional, default: true)
+    skip_forks = true
+
     # mrj will only consider repos created by the authors in this list
     # (required)
     trusted_authors = ["dependabot[bot]"]
@@ -106,6 +135,13 @@ fn printing_sample_config_works() {
     # (optional, default: empty)
     base_branch = "main"
 
+    # PRs can also be filtered by several base branches, or base branch patterns
+    # (glob syntax, where "*" doesn't match "/"); PRs whose base branch doesn't
+    # match any of these are skipped; combined with base_branch, a PR needs to
+    # satisfy both
+    # (optional, default: empty)
+    base_branches = ["main", "release/*"]
+
     # by default mrj doesn't filter PRs by head refs
     # read more on this here
     # https://docs.github.com/en/rest/pulls/pulls?apiVersion=2022-11-28#list-pull-requests--parameters
@@ -118,6 +154,26 @@ fn printing_sample_config_works() {
     # (optional, default: false)
     merge_if_blocked = true
 
+    # by default mrj doesn't merge PRs whose branch is behind the base branch
+    # if this setting is ON, mrj will update the branch of such PRs (via GitHub's
+    # "update branch" API) instead, so that they can be merged on a later run, once
+    # checks have run again
+    # (optional, default: false)
+    update_behind_branches = true
+
+    # by default mrj doesn't merge PRs that have merge conflicts
+    # if this setting is ON, mrj will ask the bot that created such PRs to rebase
+    # them (by commenting "@dependabot rebase" on Dependabot's PRs, or by ticking
+    # the rebase checkbox in the description of Renovate's PRs), so that they can
+    # be merged on a later run; mrj won't ask again while a request it made earlier
+    # is still pending, unless Dependabot hasn't acted on it within a day
+    # (optional, default: false)
+    request_rebases = true
+
+    # "checks" below refers to both check runs (eg. GitHub Actions jobs) and commit
+    # statuses (as reported by external CI systems like Jenkins or CircleCI, where
+    # the status' context acts as the check's name)
+
     # by default mrj only considers PRs where checks have either passed or are skipped
     # if this setting is OFF, mrj will not merge PRs where one or more checks have been skipped
     # (optional, default: true)
@@ -128,12 +184,191 @@ fn printing_sample_config_works() {
     # (optional, default: false)
     merge_if_checks_neutral = false
 
+    # by default mrj merges PRs as long as none of their checks have failed, which
+    # includes PRs whose checks haven't been registered yet
+    # - required_checks: only merge PRs where these checks are present; each entry
+    #   is a glob matched against a check's name (so a plain name matches exactly),
+    #   or, if prefixed with "re:", a regex matched against the whole name
+    # - min_checks: only merge PRs with at least these many checks
+    # (optional, default: empty/0)
+    required_checks = ["test", "re:lint (.+)"]
+    min_checks = 2
+
+    # checks to disregard entirely, eg. informational ones like coverage uploads or
+    # preview deployments, which might fail without it meaning anything; ignored
+    # checks don't count towards "required_checks" or "min_checks"
+    # entries are matched the same way as "required_checks"
+    # (optional, default: empty)
+    ignored_checks = ["codecov/*", "Vercel"]
+
+    # by default mrj skips PRs where checks haven't concluded yet
+    # if this is set, mrj will keep polling the checks of such a PR until they
+    # conclude, or until the timeout is up; other repos are processed in the
+    # meantime
+    # durations are written like "30s", "15m", or "1h30m"
+    # (optional, default: not set; poll_interval defaults to "30s")
+    wait_for_pending_checks = { timeout = "15m", poll_interval = "30s" }
+
+    # label rules; these let a PR be opted out of merging from GitHub's UI
+    # - required_labels: only merge PRs which have all of these labels
+    # - blocking_labels: never merge PRs which have any of these labels
+    # - freeze_label: if any open PR in a repo has this label, don't merge any PRs
+    #   in that repo (eg. add it to a PR that needs to go out first); the PR is
+    #   looked for on its own, so it needn't match "search", or be authored by a
+    #   trusted author
+    # labels are compared case-insensitively
+    # (optional, default: empty)
+    required_labels = ["dependencies"]
+    blocking_labels = ["do-not-merge", "mrj:skip"]
+    freeze_label = "merge-freeze"
+
+    # review rules; useful when branch protection requires an approving review
+    # - require_approval: only merge PRs whose head commit has been approved by a
+    #   trusted reviewer (a reviewer's latest review is what counts; approvals of
+    #   earlier commits don't count)
+    # - trusted_reviewers: whose approvals count; if empty, nobody's do (so with
+    #   require_approval on, PRs only get merged if auto_approve is on too)
+    # - auto_approve: approve PRs that pass every other rule before merging them;
+    #   if merging such a PR is blocked, it's approved, and only merged if that
+    #   unblocks it
+    # GitHub doesn't allow approving one's own PRs; approvals can be submitted with
+    # a separate token via the environment variable MRJ_APPROVAL_TOKEN
+    # an approval of the PR's latest commit that mrj submitted on an earlier run
+    # counts, so PRs don't get approved again on every run
+    # (optional, default: false/empty)
+    require_approval = false
+    trusted_reviewers = []
+    auto_approve = false
+
+    # a cool-down period, to reduce the risk of merging a freshly published
+    # malicious release; mrj will skip PRs that are newer than this, and report
+    # when they become eligible
+    # a PR's age is counted from when it was created or its head was last pushed
+    # to, or from the upstream release date if the release notes in its body carry
+    # a later one (eg. a heading like: ## [1.2.3] - 2025-03-01), whichever is
+    # latest; as release notes are written upstream, they can only make the wait
+    # longer
+    # durations are written like "72h", "3d", or "1w"
+    # (optional, default: not set)
+    min_pr_age = "72h"
+
+    # whether to skip draft PRs; these aren't meant to be merged yet
+    # (optional, default: true)
+    skip_drafts = true
+
+    # PRs which have been open for longer than this are left for a human to look
+    # at, as something is likely wrong with them
+    # (optional, default: not set)
+    max_pr_age = "30d"
+
+    # by default mrj doesn't care about the kind of upgrade a PR makes
+    # if this is set, mrj will only merge PRs where the upgrade (as parsed from
+    # Dependabot/Renovate style PR titles, eg. "bump X from 1.2.3 to 1.3.0") is
+    # of one of these types; PRs where the type can't be determined are skipped
+    # can contain: [patch, minor, major, prerelease]
+    # for versions below 1.0.0, a minor bump is considered a major one
+    # (optional, default: empty)
+    allowed_update_types = ["patch", "minor"]
+
+    # package rules; the package being upgraded is parsed from the PR title, or
+    # from Dependabot's head ref (eg. "dependabot/cargo/clap-4.5.40")
+    # if any of these are set, PRs where the package can't be determined are
+    # skipped
+    # packages are matched using globs ("*" doesn't match "/", "**" does)
+    # - allow_packages: only merge upgrades of these packages
+    # - deny_packages: never merge upgrades of these packages
+    # - deny_versions: never merge upgrades to these versions ("package@version")
+    # (optional, default: empty)
+    allow_packages = ["**"]
+    deny_packages = ["openssl*"]
+    deny_versions = ["serde@1.0.999"]
+
+    # by default mrj doesn't filter PRs by ecosystem
+    # if this is set, mrj will only merge PRs for these ecosystems; the ecosystem is
+    # taken from Dependabot's head ref (eg. "dependabot/cargo/clap-4.5.40"), or from
+    # Renovate style PR titles (eg. "update rust crate serde to v1.0.200")
+    # Dependabot's ecosystem names are used throughout (eg. "cargo",
+    # "github_actions", "npm_and_yarn", "docker", "gomod")
+    # PRs where the ecosystem can't be determined are skipped
+    # (optional, default: empty)
+    ecosystems = ["cargo", "github_actions", "npm_and_yarn"]
+
+    # by default mrj doesn't look at the files a PR changes
+    # - allowed_paths: only merge PRs where every changed file matches one of these
+    #   globs ("*" doesn't match across "/", "**" does); this guards against a bot
+    #   unexpectedly changing source files
+    # - max_changed_files: only merge PRs that change at most these many files
+    # - max_additions: only merge PRs that add at most these many lines
+    # (optional; default: empty/not set/not set)
+    allowed_paths = [
+        "**/Cargo.toml",
+        "**/Cargo.lock",
+        "**/package.json",
+        "**/package-lock.json",
+        "**/yarn.lock",
+        ".github/workflows/*",
+    ]
+    max_changed_files = 10
+    max_additions = 1000
+
     # how to merge the pull request
     # can be one of: [squash, merge, rebase]
     # make sure the choice is actually enabled in your settings
     # (required)
     merge_type = "squash"
 
+    # how qualifying PRs get merged
+    # can be one of: [direct, auto-merge, merge-queue]
+    # - direct: mrj merges PRs itself
+    # - auto-merge: mrj enables GitHub's native auto-merge on PRs; GitHub merges
+    #   them once their requirements are met (auto-merge needs to be allowed in the
+    #   repo's settings)
+    # - merge-queue: mrj adds PRs to the merge queue of their base branch, which
+    #   merges them as per its own settings (merge_type is ignored in this case)
+    # with auto-merge and merge-queue, PRs whose checks are still pending qualify
+    # as well (GitHub waits for those), and wait_for_pending_checks is ignored;
+    # PRs that have already been handed over to GitHub are left as they are, and
+    # don't count towards max_merges_per_repo
+    # (optional; default: "direct")
+    merge_strategy = "direct"
+
+    # by default the title and message of the commit created when merging a PR are
+    # left to GitHub
+    # if these are set, they're rendered as Tera templates
+    # (https://keats.github.io/tera/docs), which can refer to the PR's number,
+    # title, body, author, head_ref, and ecosystem, as well as to "upgrade" (the
+    # package being upgraded, with package, from, to, and update_type; not set if
+    # the PR isn't recognised as an upgrade)
+    # GitHub ignores these when merge_type is "rebase", and when PRs are added to
+    # a merge queue
+    # (optional; default: not set)
+    commit_title_template = "{% if upgrade %}chore(deps): bump {{ upgrade.package }} to {{ upgrade.to }}{% else %}{{ title }}{% endif %} (#{{ number }})"
+    commit_message_template = "Merged-by: mrj"
+
+    # by default mrj merges at most one PR per repo per run
+    # - max_merges_per_repo: merge up to these many PRs per repo per run; after
+    #   each merge, mrj waits for GitHub to recompute the mergeable state of the
+    #   next PR, and updates its branch if the merge put it behind the base branch
+    #   (regardless of update_behind_branches)
+    # - merge_delay: how long to wait after each merge before moving on to the next
+    #   PR of the repo, so as to not set off a storm of CI runs
+    # (optional; default: 1/not set)
+    max_merges_per_repo = 3
+    merge_delay = "1m"
+
+    # what to do after merging a PR (these only apply to PRs merged by mrj itself,
+    # ie. when merge_strategy is "direct")
+    # - delete_branch_after_merge: delete the PR's head branch (useful if the
+    #   repo doesn't have GitHub's "Automatically delete head branches" setting
+    #   on; branches of forks are left alone)
+    # - close_superseded: close the older open PRs by trusted authors that upgrade
+    #   the same package (as detected from the PR's title or head branch) in the
+    #   same directory and against the same base branch as the merged PR, to a
+    #   version no newer than the merged one, with a comment explaining why
+    # (optional; default: false)
+    delete_branch_after_merge = false
+    close_superseded = false
+
     # what to sort pull requests by
     # can be one of: created, updated, popularity, long-running
     # "popularity" will sort by the number of comments
@@ -149,6 +384,83 @@ fn printing_sample_config_works() {
     # (optional; default: asc)
     sort_direction = "asc"
 
+    # the maximum number of pages (of 100 items each) to fetch when listing search
+    # results, an owner's repos, a repo's open PRs, and a PR's checks, changed
+    # files, reviews, and comments
+    # search results and open PRs beyond this are left for the next run; an owner
+    # with more repos than this fails with an error, as does a PR with more checks
+    # (or any of the others) than this, so that a failing check is never missed
+    # (optional; default: 10)
+    max_pages = 10
+
+    # rules are small boolean expressions over a PR's attributes, in the form
+    # "<allow|deny> if <condition>"; they're evaluated in order once a PR's checks
+    # have been looked at, and the first one that matches decides: "deny" skips the
+    # PR, while "allow" lets it move on to the remaining steps (approval, mergeable
+    # state, etc.); if there are allow rules and none of them matches, the PR is
+    # skipped
+    # the attributes are:
+    # - author, title, head, base, ecosystem, package: compared via ==, !=,
+    #   contains, and matches (which takes a regex)
+    # - labels, files, checks (the ones that concluded successfully): compared via
+    #   contains, and matches (which holds if any of the values match)
+    # - update_type: compared via == and != with "major", "minor", "patch", or
+    #   "prerelease"
+    # - changed_files, additions: compared with numbers via ==, !=, <, <=, >, and >=
+    # - age: compared with durations, eg. "3d", via <, <=, >, and >=
+    # conditions can be combined via &&, ||, !, and parentheses; a value that isn't
+    # known for a PR (eg. the update type of a PR that isn't an upgrade) only
+    # satisfies != comparisons, so that eg. 'deny if update_type != "patch"' also
+    # denies PRs whose update type is unknown
+    # (optional, default: empty)
+    [[rules]]
+    name = "no security fixes"
+    rule = 'deny if title contains "[security]" || labels contains "security"'
+
+    [[rules]]
+    name = "patch bumps for cargo"
+    rule = 'allow if update_type == "patch" && ecosystem == "cargo"'
+
+    # settings can be overridden for all repos of an owner (via [owner."<owner>"],
+    # or [owners."<owner>"]), for a single repo (via [repo."<owner>/<repo>"]), for
+    # PRs against base branches matching a pattern (via [base."<pattern>"]), or for
+    # PRs of an ecosystem (via [ecosystem."<ecosystem>"]); unknown keys are
+    # rejected, so a misspelt table doesn't go unnoticed
+    # the following settings can be overridden: trusted_authors, base_branch,
+    # base_branches, head_pattern, merge_if_blocked, update_behind_branches,
+    # request_rebases, merge_if_checks_skipped, merge_if_checks_neutral,
+    # required_checks, min_checks, ignored_checks, wait_for_pending_checks,
+    # required_labels, blocking_labels, freeze_label, require_approval,
+    # trusted_reviewers, auto_approve, min_pr_age, skip_drafts, max_pr_age,
+    # allowed_update_types, allow_packages, deny_packages, deny_versions,
+    # ecosystems, allowed_paths, max_changed_files, max_additions, rules,
+    # merge_type, merge_strategy, commit_title_template, commit_message_template,
+    # max_merges_per_repo, merge_delay, delete_branch_after_merge, close_superseded
+    # ecosystem overrides take precedence over base branch overrides, which take
+    # precedence over repo overrides, which take precedence over owner overrides,
+    # which in turn take precedence over the settings above; if several base branch
+    # patterns match a PR, they're applied in the order they're declared in, so the
+    # last one declared takes precedence (eg. [base."release/*"] followed by
+    # [base."release/1.x"] for an exception to the general release branch rules);
+    # base_branch, base_branches, freeze_label, max_merges_per_repo, and
+    # merge_delay can't be overridden per base branch or ecosystem
+    # (optional, default: empty)
+    [owner."owner"]
+    trusted_authors = ["dependabot[bot]", "renovate[bot]"]
+    require_approval = true
+    auto_approve = true
+
+    [repo."owner/repo-2"]
+    base_branch = "develop"
+    ignored_checks = ["preview-*"]
+    merge_type = "rebase"
+
+    [base."release/*"]
+    allowed_update_types = ["patch"]
+
+    [ecosystem."npm_and_yarn"]
+    allowed_update_types = ["patch"]
+
     ----- stderr -----
     "#);
 }
@@ -202,7 +514,7 @@ fn parsing_invalid_config_fails() {
       |
     1 | repos = "not a list"
       |         ^^^^^^^^^^^^
-    invalid type: string "not a list", expected a sequence
+    invalid type: string "not a list", expected a list of repos
     "#);
 }
 
@@ -229,6 +541,6 @@ fn fails_if_invalid_repos_provided_via_config() {
       |
     3 |     "invalid-repo",
       |     ^^^^^^^^^^^^^^
-    invalid value: string "invalid-repo", expected a value in the form "owner/repo"
+    invalid value: string "invalid-repo", expected a value in the form "owner/repo" or "owner/<glob>", or a table in the form { owner = "owner", topic = "topic" }
     "#);
 }
@@ -37,6 +37,8 @@ fn debug_mode_works() {
     show repos with no prs:               false
     show prs from untrusted authors:      false
     show prs with unmatched head:         false
+    show draft prs:                       false
+    show stale prs:                       false
     execute:                              false
     plain stdout:                         false
 
@@ -76,6 +78,8 @@ fn overriding_repos_works() {
     show repos with no prs:               false
     show prs from untrusted authors:      false
     show prs with unmatched head:         false
+    show draft prs:                       false
+    show stale prs:                       false
     execute:                              false
     plain stdout:                         false
 